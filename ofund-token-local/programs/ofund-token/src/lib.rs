@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_lang::solana_program::keccak;
+use anchor_lang::system_program;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
 
 // This is your program's public key and it will update
 // automatically when you build the project.
@@ -53,6 +55,59 @@ pub mod otonom_program {
 
     // Register a new user and grant initial tokens
     pub fn register_user(ctx: Context<RegisterUser>, user_bump: u8) -> Result<()> {
+        require!(
+            ctx.accounts.blocklist_entry.data_is_empty(),
+            OtonomError::WalletBlocked
+        );
+        // Registration (and its grant) is one-shot per wallet. The marker outlives
+        // the profile, so closing a profile does not allow registering again.
+        let registration = &mut ctx.accounts.registration;
+        require!(
+            registration.user == Pubkey::default()
+                && ctx.accounts.user_profile.user == Pubkey::default(),
+            OtonomError::AlreadyRegistered
+        );
+        registration.bump = ctx.bumps.registration;
+        registration.user = ctx.accounts.user.key();
+        registration.registered_at = Clock::get()?.unix_timestamp;
+
+        // Enforce the per-window registration cap
+        let now = Clock::get()?.unix_timestamp;
+        let limiter = &mut ctx.accounts.registration_limiter;
+        if now >= limiter.window_start.saturating_add(limiter.window_duration) {
+            limiter.window_start = now;
+            limiter.registrations_in_window = 0;
+        }
+        require!(
+            limiter.max_registrations_per_window == 0
+                || limiter.registrations_in_window < limiter.max_registrations_per_window,
+            OtonomError::RegistrationCapReached
+        );
+        limiter.registrations_in_window = limiter
+            .registrations_in_window
+            .checked_add(1)
+            .ok_or(error!(OtonomError::ArithmeticOverflow))?;
+
+        // Collect the optional registration fee for the treasury
+        if limiter.registration_fee > 0 {
+            let cpi_accounts = system_program::Transfer {
+                from: ctx.accounts.user.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+            system_program::transfer(cpi_ctx, limiter.registration_fee)?;
+        }
+
+        let stats = &mut ctx.accounts.global_stats;
+        stats.user_count = stats
+            .user_count
+            .checked_add(1)
+            .ok_or(error!(OtonomError::ArithmeticOverflow))?;
+        stats.total_fees = stats
+            .total_fees
+            .checked_add(limiter.registration_fee)
+            .ok_or(error!(OtonomError::ArithmeticOverflow))?;
+
         let user_profile = &mut ctx.accounts.user_profile;
         user_profile.user = ctx.accounts.user.key();
         user_profile.bump = user_bump;
@@ -60,6 +115,7 @@ pub mod otonom_program {
         // Initialize with Tier 0
         user_profile.tier = 0;
         user_profile.total_invested = 0;
+        user_profile.loyalty_points = 0;
 
         // Grant initial OFUND tokens for testing (100,000)
         let initial_grant = 100_000 * 10u64.pow(9); // 9 decimals
@@ -73,7 +129,7 @@ pub mod otonom_program {
         ];
         let signer = &[&seeds[..]];
 
-        let cpi_accounts = token::MintTo {
+        let cpi_accounts = token_interface::MintTo {
             mint: ctx.accounts.mint.to_account_info(),
             to: ctx.accounts.user_token_account.to_account_info(),
             authority: ctx.accounts.mint_authority_pda.to_account_info(),
@@ -81,10 +137,53 @@ pub mod otonom_program {
         let cpi_program = ctx.accounts.token_program.to_account_info();
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
 
-        token::mint_to(cpi_ctx, initial_grant)?;
+        token_interface::mint_to(cpi_ctx, initial_grant)?;
+        add_granted(&mut ctx.accounts.global_stats, initial_grant)?;
+
+        // Tiers follow the invested OFUND total, not the grant, so a new user
+        // starts at the base level and records it in their tier history
+        update_tier(user_profile, &ctx.accounts.tier_config, Clock::get()?.unix_timestamp);
+
+        // Credit the referrer, if one was provided
+        if let Some(referrer_profile) = ctx.accounts.referrer_profile.as_mut() {
+            require!(
+                referrer_profile.user != ctx.accounts.user.key(),
+                OtonomError::SelfReferral
+            );
+            let referrer_token_account = ctx
+                .accounts
+                .referrer_token_account
+                .as_ref()
+                .ok_or(error!(OtonomError::InvalidTokenAccount))?;
+            require!(
+                referrer_token_account.owner == referrer_profile.user
+                    && referrer_token_account.mint == ctx.accounts.mint.key(),
+                OtonomError::InvalidTokenAccount
+            );
 
-        // Update user tier based on new balance
-        user_profile.tier = calculate_tier(initial_grant);
+            let cpi_accounts = token_interface::MintTo {
+                mint: ctx.accounts.mint.to_account_info(),
+                to: referrer_token_account.to_account_info(),
+                authority: ctx.accounts.mint_authority_pda.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+
+            token_interface::mint_to(cpi_ctx, REFERRAL_BONUS)?;
+            add_granted(&mut ctx.accounts.global_stats, REFERRAL_BONUS)?;
+
+            referrer_profile.referral_count = referrer_profile
+                .referral_count
+                .checked_add(1)
+                .ok_or(error!(OtonomError::ArithmeticOverflow))?;
+            referrer_profile.referral_rewards = referrer_profile
+                .referral_rewards
+                .checked_add(REFERRAL_BONUS)
+                .ok_or(error!(OtonomError::ArithmeticOverflow))?;
+            user_profile.referrer = Some(referrer_profile.user);
+
+            msg!("Referral bonus credited to {}", referrer_profile.user);
+        }
 
         msg!("User registered successfully and granted initial tokens");
         Ok(())
@@ -94,53 +193,299 @@ pub mod otonom_program {
     pub fn initialize_project(
         ctx: Context<InitializeProject>,
         project_name: String,
-        project_bump: u8,
     ) -> Result<()> {
+        require!(
+            !project_name.is_empty() && project_name.len() <= MAX_PROJECT_NAME_LEN,
+            OtonomError::InvalidProjectName
+        );
+
+        // Projects are keyed by the next sequential ID rather than by name
         let project = &mut ctx.accounts.project;
+        project.id = ctx.accounts.global_stats.project_count;
         project.name = project_name;
-        project.bump = project_bump;
+        project.bump = ctx.bumps.project;
         project.authority = ctx.accounts.authority.key();
         project.vault = ctx.accounts.project_vault.key();
+        project.mint = ctx.accounts.mint.key();
+        project.payment_mint = ctx.accounts.payment_mint.key();
         project.total_raised = 0;
+        project.status = ProjectStatus::Active;
+        project.has_lottery = false;
+        project.sale_mode = SaleMode::Standard;
+        project.hard_cap = 0;
+        project.start_time = 0;
+        project.end_time = 0;
+        project.proceeds_withdrawn = 0;
+        project.unsettled_contributions = 0;
+        project.tier_snapshot_time = 0;
+        project.min_tier_hold = 0;
+        project.requires_kyc = false;
+        project.metadata = ProjectMetadata::default();
+
+        let stats = &mut ctx.accounts.global_stats;
+        stats.project_count = stats
+            .project_count
+            .checked_add(1)
+            .ok_or(error!(OtonomError::ArithmeticOverflow))?;
 
-        msg!("Project initialized: {}", project.name);
+        msg!("Project {} initialized: {}", project.id, project.name);
         Ok(())
     }
 
     // Invest in a project
     pub fn invest_in_project(ctx: Context<InvestInProject>, amount: u64) -> Result<()> {
-        let user_profile = &mut ctx.accounts.user_profile;
-        let project = &mut ctx.accounts.project;
+        require!(amount > 0, OtonomError::ZeroInvestment);
+        require!(
+            ctx.accounts.project.status == ProjectStatus::Active,
+            OtonomError::ProjectNotActive
+        );
+        require!(!ctx.accounts.project.paused, OtonomError::ProjectPaused);
+        require!(
+            ctx.accounts.project.end_time != 0,
+            OtonomError::SaleNotConfigured
+        );
+        require!(
+            amount >= ctx.accounts.project.min_investment,
+            OtonomError::InvestmentBelowMinimum
+        );
+        require!(
+            ctx.accounts.project.max_investment == 0
+                || amount <= ctx.accounts.project.max_investment,
+            OtonomError::InvestmentAboveMaximum
+        );
+        require!(
+            !sale_ended(&ctx.accounts.project, Clock::get()?.unix_timestamp),
+            OtonomError::SaleEnded
+        );
+        require!(
+            Clock::get()?.unix_timestamp >= ctx.accounts.project.start_time,
+            OtonomError::SaleNotStarted
+        );
+
+        // Tier perks: higher tiers open earlier and get a larger allocation
+        let benefit = tier_benefit(
+            &ctx.accounts.project,
+            &ctx.accounts.user_profile,
+            ctx.accounts.tier_snapshot.as_deref(),
+        )?;
+        require!(
+            Clock::get()?.unix_timestamp
+                >= ctx.accounts.project.start_time.saturating_add(benefit.access_delay),
+            OtonomError::EarlyAccessNotOpen
+        );
+        require!(
+            ctx.accounts.blocklist_entry.data_is_empty(),
+            OtonomError::WalletBlocked
+        );
+
+        // KYC-gated projects require a current, unrevoked attestation from the
+        // current attester, so rotating a compromised attester retires its attestations
+        if ctx.accounts.project.requires_kyc {
+            let attestation = ctx
+                .accounts
+                .attestation
+                .as_ref()
+                .ok_or(error!(OtonomError::AttestationRequired))?;
+            let kyc_config = ctx
+                .accounts
+                .kyc_config
+                .as_ref()
+                .ok_or(error!(OtonomError::KycConfigRequired))?;
+            require!(
+                attestation.attester == kyc_config.attester,
+                OtonomError::AttesterMismatch
+            );
+            require!(!attestation.revoked, OtonomError::AttestationRevoked);
+            require!(
+                Clock::get()?.unix_timestamp < attestation.expires_at,
+                OtonomError::AttestationExpired
+            );
+        }
+
+        // Lottery projects only accept investments from winners, within their allocation
+        if ctx.accounts.project.has_lottery {
+            let lottery = ctx
+                .accounts
+                .lottery
+                .as_ref()
+                .ok_or(error!(OtonomError::LotteryTicketRequired))?;
+            let ticket = ctx
+                .accounts
+                .lottery_ticket
+                .as_mut()
+                .ok_or(error!(OtonomError::LotteryTicketRequired))?;
+            require!(lottery.drawn, OtonomError::LotteryNotDrawn);
+            require!(
+                Clock::get()?.unix_timestamp <= lottery.investment_end,
+                OtonomError::LotteryWindowClosed
+            );
+
+            // Wins are fixed once the lottery is drawn, so count them on the first
+            // investment and reuse the stored count after that
+            let wins = match ticket.wins {
+                Some(wins) => wins,
+                None => {
+                    let wins =
+                        count_winning_tickets(lottery, ticket.first_ticket, ticket.ticket_count);
+                    ticket.wins = Some(wins);
+                    wins
+                }
+            };
+            let allocation = wins
+                .checked_mul(lottery.allocation_per_ticket)
+                .ok_or(error!(OtonomError::ArithmeticOverflow))?;
+            ticket.invested = ticket
+                .invested
+                .checked_add(amount)
+                .ok_or(error!(OtonomError::ArithmeticOverflow))?;
+            require!(
+                ticket.invested <= allocation,
+                OtonomError::LotteryAllocationExceeded
+            );
+        }
 
         // Transfer tokens from investor to project vault
-        let cpi_accounts = token::Transfer {
+        let vault_balance_before = ctx.accounts.project_vault.amount;
+        let cpi_accounts = token_interface::TransferChecked {
             from: ctx.accounts.investor_token_account.to_account_info(),
+            mint: ctx.accounts.payment_mint.to_account_info(),
             to: ctx.accounts.project_vault.to_account_info(),
             authority: ctx.accounts.investor.to_account_info(),
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
         let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
 
-        token::transfer(cpi_ctx, amount)?;
+        token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.payment_mint.decimals)?;
 
-        // Update user's total invested amount
-        user_profile.total_invested = user_profile
-            .total_invested
-            .checked_add(amount)
+        // Record what the vault actually received, which is less than `amount`
+        // when the payment mint charges a Token-2022 transfer fee
+        ctx.accounts.project_vault.reload()?;
+        let amount = ctx
+            .accounts
+            .project_vault
+            .amount
+            .checked_sub(vault_balance_before)
             .ok_or(error!(OtonomError::ArithmeticOverflow))?;
 
-        // Update project's total raised
+        let user_profile = &mut ctx.accounts.user_profile;
+        let project = &mut ctx.accounts.project;
+
+        // Update user's per-currency total for the payment mint
+        let payment_mint = ctx.accounts.payment_mint.key();
+        match user_profile
+            .currency_totals
+            .iter_mut()
+            .find(|total| total.mint == payment_mint)
+        {
+            Some(total) => {
+                total.amount = total
+                    .amount
+                    .checked_add(amount)
+                    .ok_or(error!(OtonomError::ArithmeticOverflow))?;
+            }
+            None => {
+                require!(
+                    user_profile.currency_totals.len() < MAX_CURRENCIES,
+                    OtonomError::MaxCurrenciesReached
+                );
+                user_profile.currency_totals.push(CurrencyTotal {
+                    mint: payment_mint,
+                    amount,
+                });
+            }
+        }
+
+        // Only OFUND-denominated investments count towards the tier. Projects pick
+        // their own mint, so compare against the canonical OFUND mint.
+        if payment_mint == ctx.accounts.program_config.ofund_mint {
+            user_profile.total_invested = user_profile
+                .total_invested
+                .checked_add(amount)
+                .ok_or(error!(OtonomError::ArithmeticOverflow))?;
+
+            update_tier(user_profile, &ctx.accounts.tier_config, Clock::get()?.unix_timestamp);
+
+            let stats = &mut ctx.accounts.global_stats;
+            stats.total_ofund_raised = stats
+                .total_ofund_raised
+                .checked_add(amount)
+                .ok_or(error!(OtonomError::ArithmeticOverflow))?;
+        }
+
+        // Update project's total raised (in payment mint units).
+        // Standard sales reject anything above the hard cap; overflow sales accept
+        // every deposit and refund the excess in settle_overflow.
         project.total_raised = project
             .total_raised
             .checked_add(amount)
             .ok_or(error!(OtonomError::ArithmeticOverflow))?;
+        if project.sale_mode == SaleMode::Standard && project.hard_cap > 0 {
+            require!(
+                project.total_raised <= project.hard_cap,
+                OtonomError::HardCapExceeded
+            );
+        }
+
+        // Track the investor's running contribution to this project
+        let contribution = &mut ctx.accounts.contribution;
+        if contribution.user == Pubkey::default() {
+            contribution.bump = ctx.bumps.contribution;
+            contribution.project = project.key();
+            contribution.user = ctx.accounts.investor.key();
+            contribution.amount = 0;
+            contribution.accepted = 0;
+            contribution.settled = false;
+            contribution.refunded = false;
+            contribution.points_earned = 0;
+        }
+        if contribution.amount == 0 {
+            project.open_contributions = project
+                .open_contributions
+                .checked_add(1)
+                .ok_or(error!(OtonomError::ArithmeticOverflow))?;
+            // Overflow contributions holding a deposit must each be settled,
+            // including one topped up again after a full withdrawal
+            if project.sale_mode == SaleMode::Overflow {
+                project.unsettled_contributions = project
+                    .unsettled_contributions
+                    .checked_add(1)
+                    .ok_or(error!(OtonomError::ArithmeticOverflow))?;
+            }
+        }
+        // Sale config is locked from the first deposit, even once it is withdrawn
+        project.has_deposits = true;
+        contribution.amount = contribution
+            .amount
+            .checked_add(amount)
+            .ok_or(error!(OtonomError::ArithmeticOverflow))?;
 
-        // Update user tier based on new total
-        user_profile.tier = calculate_tier(user_profile.total_invested);
+        // The tier-weighted allocation caps the investor's running total, so
+        // splitting an investment across transactions does not get around it
+        require!(
+            project.max_allocation == 0
+                || u128::from(contribution.amount) * u128::from(BPS_DENOMINATOR)
+                    <= u128::from(project.max_allocation) * u128::from(benefit.weight_bps),
+            OtonomError::AllocationExceeded
+        );
+
+        // Accrue non-transferable loyalty points for the investment. They stay
+        // pending on the contribution until the project settles, so a deposit
+        // that is later withdrawn or refunded never counts towards bonus tickets.
+        let points = loyalty_points_for(
+            &ctx.accounts.points_config,
+            payment_mint,
+            amount,
+            ctx.accounts.payment_mint.decimals,
+        );
+        contribution.points_earned = contribution
+            .points_earned
+            .checked_add(points)
+            .ok_or(error!(OtonomError::ArithmeticOverflow))?;
 
         // Record this investment for on-chain portfolio history
         user_profile.investments.push(Investment {
             project: project.key(),
+            mint: payment_mint,
             amount,
             timestamp: Clock::get()?.unix_timestamp,
         });
@@ -153,218 +498,4266 @@ pub mod otonom_program {
         );
         Ok(())
     }
-}
 
-// Calculate tier based on token balance
-fn calculate_tier(balance: u64) -> u8 {
-    let tier1_threshold = 1_000 * 10u64.pow(9);
-    let tier2_threshold = 10_000 * 10u64.pow(9);
-    let tier3_threshold = 100_000 * 10u64.pow(9);
+    // Create a Merkle distributor for a targeted OFUND airdrop
+    pub fn initialize_distributor(
+        ctx: Context<InitializeDistributor>,
+        distributor_id: u64,
+        merkle_root: [u8; 32],
+        max_total_claim: u64,
+        max_num_nodes: u64,
+    ) -> Result<()> {
+        require!(
+            has_role(
+                &ctx.accounts.mint_authority,
+                ctx.accounts.roles.as_deref(),
+                ctx.accounts.operator.key(),
+                ROLE_GRANT_OPERATOR,
+            ),
+            OtonomError::Unauthorized
+        );
+
+        let distributor = &mut ctx.accounts.distributor;
+        distributor.bump = ctx.bumps.distributor;
+        distributor.mint = ctx.accounts.mint.key();
+        distributor.creator = ctx.accounts.operator.key();
+        distributor.distributor_id = distributor_id;
+        distributor.merkle_root = merkle_root;
+        distributor.max_total_claim = max_total_claim;
+        distributor.max_num_nodes = max_num_nodes;
+        distributor.total_amount_claimed = 0;
+        distributor.num_nodes_claimed = 0;
+        distributor.claimed_bitmap = vec![0u8; bitmap_len(max_num_nodes)];
 
-    if balance >= tier3_threshold {
-        3
-    } else if balance >= tier2_threshold {
-        2
-    } else if balance >= tier1_threshold {
-        1
-    } else {
-        0
+        msg!(
+            "Distributor {} initialized for up to {} claims",
+            distributor_id,
+            max_num_nodes
+        );
+        Ok(())
     }
-}
 
-// Constants for user profile size and investment cap
-const MAX_INVESTS: usize = 20; // maximum number of investments stored per user
-const INVEST_SIZE: usize = 32 + 8 + 8; // Pubkey (32) + amount (u64) + timestamp (i64)
-const USER_PROFILE_SPACE: usize =
-    8  + // discriminator
-    32 + // user pubkey
-    1  + // bump
-    1  + // tier
-    8  + // total_invested
-    4  + // vec length prefix (Anchor serialises Vec with u32 length)
-    MAX_INVESTS * INVEST_SIZE;
+    // Claim an airdrop allocation proven against the distributor's Merkle root
+    pub fn claim_airdrop(
+        ctx: Context<ClaimAirdrop>,
+        index: u64,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let distributor = &mut ctx.accounts.distributor;
+        require!(
+            index < distributor.max_num_nodes,
+            OtonomError::InvalidClaimIndex
+        );
 
-// Investment struct
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct Investment {
-    pub project: Pubkey,
-    pub amount: u64,
-    pub timestamp: i64,
+        let byte = (index / 8) as usize;
+        let bit = 1u8 << (index % 8);
+        require!(
+            distributor.claimed_bitmap[byte] & bit == 0,
+            OtonomError::AlreadyClaimed
+        );
+
+        let leaf = airdrop_leaf(index, &ctx.accounts.claimant.key(), amount);
+        require!(
+            verify_merkle_proof(&proof, distributor.merkle_root, leaf),
+            OtonomError::InvalidProof
+        );
+
+        let total_amount_claimed = distributor
+            .total_amount_claimed
+            .checked_add(amount)
+            .ok_or(error!(OtonomError::ArithmeticOverflow))?;
+        require!(
+            total_amount_claimed <= distributor.max_total_claim,
+            OtonomError::ExceededMaxClaim
+        );
+
+        distributor.claimed_bitmap[byte] |= bit;
+        distributor.total_amount_claimed = total_amount_claimed;
+        distributor.num_nodes_claimed = distributor
+            .num_nodes_claimed
+            .checked_add(1)
+            .ok_or(error!(OtonomError::ArithmeticOverflow))?;
+
+        // Mint the claimed amount through the mint-authority PDA
+        let mint_key = ctx.accounts.mint.key();
+        let seeds = &[
+            b"mint-authority".as_ref(),
+            mint_key.as_ref(),
+            &[ctx.bumps.mint_authority_pda],
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = token_interface::MintTo {
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.claimant_token_account.to_account_info(),
+            authority: ctx.accounts.mint_authority_pda.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+
+        token_interface::mint_to(cpi_ctx, amount)?;
+        add_granted(&mut ctx.accounts.global_stats, amount)?;
+
+        msg!("Airdrop claim {} processed: {} tokens", index, amount);
+        Ok(())
+    }
+
+    // End a project's raise so no further investments are accepted
+    pub fn settle_project(ctx: Context<SettleProject>) -> Result<()> {
+        let project = &mut ctx.accounts.project;
+        require!(
+            project.status == ProjectStatus::Active,
+            OtonomError::ProjectNotActive
+        );
+        // Sales settle only once the window closes, or a standard sale fills its hard
+        // cap, so an owner cannot settle mid-raise to escape cancellation
+        let cap_filled = project.sale_mode == SaleMode::Standard
+            && project.hard_cap > 0
+            && project.total_raised >= project.hard_cap;
+        require!(
+            cap_filled || sale_ended(project, Clock::get()?.unix_timestamp),
+            OtonomError::SaleNotEnded
+        );
+        // Overflow sales also need every refund paid
+        if project.sale_mode == SaleMode::Overflow {
+            require!(
+                project.unsettled_contributions == 0,
+                OtonomError::OverflowNotSettled
+            );
+        }
+        project.status = ProjectStatus::Settled;
+
+        msg!("Project {} settled with {} raised", project.name, project.total_raised);
+        Ok(())
+    }
+
+    // Close a settled or cancelled project once its vault is empty and return rent to the authority
+    pub fn close_project(ctx: Context<CloseProject>) -> Result<()> {
+        require!(
+            ctx.accounts.project.status != ProjectStatus::Active,
+            OtonomError::ProjectNotSettled
+        );
+        require!(
+            ctx.accounts.project_vault.amount == 0,
+            OtonomError::VaultNotEmpty
+        );
+        require!(
+            ctx.accounts.project.status != ProjectStatus::Cancelled
+                || ctx.accounts.project.open_contributions == 0,
+            OtonomError::RefundsOutstanding
+        );
+
+        // Close the empty escrow vault, signed by the project PDA, returning its
+        // rent to the authority
+        let project = &ctx.accounts.project;
+        let id = project.id.to_le_bytes();
+        let seeds = &[b"project".as_ref(), project.mint.as_ref(), &id, &[project.bump]];
+        let signer = &[&seeds[..]];
+        let cpi_accounts = token_interface::CloseAccount {
+            account: ctx.accounts.project_vault.to_account_info(),
+            destination: ctx.accounts.authority.to_account_info(),
+            authority: project.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        );
+        token_interface::close_account(cpi_ctx)?;
+
+        // Release the project's name reservation, if it holds one
+        let name_info = ctx.accounts.project_name.to_account_info();
+        if name_info.owner == &crate::ID && !name_info.data_is_empty() {
+            let name_record = ProjectName::try_deserialize(&mut &name_info.try_borrow_data()?[..])?;
+            if name_record.project == ctx.accounts.project.key() {
+                close_program_account(&name_info, &ctx.accounts.authority.to_account_info())?;
+            }
+        }
+
+        msg!("Project {} closed", ctx.accounts.project.name);
+        Ok(())
+    }
+
+    // Close a user profile and return rent to the user.
+    // Every project the user has invested in must be passed in remaining_accounts
+    // and must no longer hold an open position (settled or already closed).
+    // For cancelled projects the user's contribution must also be passed and have
+    // no refund left to claim, since claim_refund needs the profile.
+    pub fn close_user_profile(ctx: Context<CloseUserProfile>) -> Result<()> {
+        let user_profile = &ctx.accounts.user_profile;
+
+        for investment in user_profile.investments.iter() {
+            let project_info = ctx
+                .remaining_accounts
+                .iter()
+                .find(|info| info.key() == investment.project)
+                .ok_or(error!(OtonomError::MissingProjectAccount))?;
+
+            match project_status(project_info)? {
+                Some(ProjectStatus::Active) => return err!(OtonomError::OpenPositionsRemain),
+                Some(ProjectStatus::Cancelled) => {
+                    let (contribution_key, _) = Pubkey::find_program_address(
+                        &[
+                            b"contribution",
+                            investment.project.as_ref(),
+                            user_profile.user.as_ref(),
+                        ],
+                        &crate::ID,
+                    );
+                    let contribution_info = ctx
+                        .remaining_accounts
+                        .iter()
+                        .find(|info| info.key() == contribution_key)
+                        .ok_or(error!(OtonomError::MissingContributionAccount))?;
+                    // A closed contribution has already been refunded
+                    if contribution_info.owner == &crate::ID && !contribution_info.data_is_empty() {
+                        let contribution = Contribution::try_deserialize(
+                            &mut &contribution_info.try_borrow_data()?[..],
+                        )?;
+                        require!(refund_due(&contribution) == 0, OtonomError::RefundUnclaimed);
+                    }
+                }
+                Some(ProjectStatus::Settled) | None => {}
+            }
+        }
+
+        msg!("User profile closed for {}", user_profile.user);
+        Ok(())
+    }
+
+    // Configure a lottery for an oversubscribed raise; investing becomes winners-only
+    pub fn initialize_lottery(
+        ctx: Context<InitializeLottery>,
+        registration_end: i64,
+        investment_end: i64,
+        winning_tickets: u64,
+        allocation_per_ticket: u64,
+    ) -> Result<()> {
+        let project = &mut ctx.accounts.project;
+        require!(
+            project.status == ProjectStatus::Active,
+            OtonomError::ProjectNotActive
+        );
+        require!(!project.has_deposits, OtonomError::InvalidLotteryConfig);
+        require!(
+            registration_end > Clock::get()?.unix_timestamp
+                && investment_end > registration_end
+                && winning_tickets > 0
+                && allocation_per_ticket > 0,
+            OtonomError::InvalidLotteryConfig
+        );
+
+        let lottery = &mut ctx.accounts.lottery;
+        lottery.bump = ctx.bumps.lottery;
+        lottery.project = project.key();
+        lottery.registration_end = registration_end;
+        lottery.investment_end = investment_end;
+        lottery.winning_tickets = winning_tickets;
+        lottery.allocation_per_ticket = allocation_per_ticket;
+        lottery.total_tickets = 0;
+        lottery.commit_slot = 0;
+        lottery.seed = [0u8; 32];
+        lottery.drawn = false;
+
+        project.has_lottery = true;
+
+        msg!("Lottery configured for project {}", project.name);
+        Ok(())
+    }
+
+    // Register interest in a lottery; ticket count is based on the user's tier
+    pub fn register_lottery_tickets(ctx: Context<RegisterLotteryTickets>) -> Result<()> {
+        let lottery = &mut ctx.accounts.lottery;
+        require!(
+            Clock::get()?.unix_timestamp < lottery.registration_end,
+            OtonomError::LotteryRegistrationClosed
+        );
+
+        let tier = effective_tier(
+            &ctx.accounts.project,
+            &ctx.accounts.user_profile,
+            ctx.accounts.tier_snapshot.as_deref(),
+        )?;
+        let ticket_count = tickets_for_tier(&ctx.accounts.tier_config, tier)
+            .checked_add(bonus_lottery_tickets(
+                &ctx.accounts.points_config,
+                &ctx.accounts.user_profile,
+            ))
+            .ok_or(error!(OtonomError::ArithmeticOverflow))?
+            .min(MAX_TICKETS_PER_USER);
+
+        let ticket = &mut ctx.accounts.lottery_ticket;
+        ticket.bump = ctx.bumps.lottery_ticket;
+        ticket.project = lottery.project;
+        ticket.user = ctx.accounts.user.key();
+        ticket.first_ticket = lottery.total_tickets;
+        ticket.ticket_count = ticket_count;
+        ticket.invested = 0;
+        ticket.wins = None;
+
+        lottery.total_tickets = lottery
+            .total_tickets
+            .checked_add(ticket_count)
+            .ok_or(error!(OtonomError::ArithmeticOverflow))?;
+
+        msg!("Registered {} lottery tickets", ticket_count);
+        Ok(())
+    }
+
+    // Commit to a future slot whose hash will seed the draw
+    pub fn commit_lottery_draw(ctx: Context<CommitLotteryDraw>) -> Result<()> {
+        let lottery = &mut ctx.accounts.lottery;
+        require!(!lottery.drawn, OtonomError::LotteryAlreadyDrawn);
+        require!(
+            Clock::get()?.unix_timestamp >= lottery.registration_end,
+            OtonomError::LotteryRegistrationOpen
+        );
+
+        // A commitment can only be replaced once its slot hash has expired
+        let current_slot = Clock::get()?.slot;
+        require!(
+            lottery.commit_slot == 0
+                || current_slot > lottery.commit_slot.saturating_add(MAX_SLOT_HASH_AGE),
+            OtonomError::DrawAlreadyCommitted
+        );
+
+        lottery.commit_slot = current_slot
+            .checked_add(LOTTERY_COMMIT_DELAY_SLOTS)
+            .ok_or(error!(OtonomError::ArithmeticOverflow))?;
+
+        msg!("Lottery draw committed to slot {}", lottery.commit_slot);
+        Ok(())
+    }
+
+    // Draw the lottery using the hash of the committed slot
+    pub fn draw_lottery(ctx: Context<DrawLottery>) -> Result<()> {
+        let lottery = &mut ctx.accounts.lottery;
+        require!(!lottery.drawn, OtonomError::LotteryAlreadyDrawn);
+        require!(lottery.commit_slot != 0, OtonomError::DrawNotCommitted);
+        require!(
+            Clock::get()?.slot > lottery.commit_slot,
+            OtonomError::DrawSlotNotReached
+        );
+
+        let slot_hash = find_slot_hash(&ctx.accounts.slot_hashes, lottery.commit_slot)?;
+        lottery.seed = keccak::hashv(&[&slot_hash, lottery.project.as_ref()]).0;
+        lottery.drawn = true;
+
+        msg!(
+            "Lottery drawn: {} winning tickets out of {}",
+            lottery.winning_tickets.min(lottery.total_tickets),
+            lottery.total_tickets
+        );
+        Ok(())
+    }
+
+    // Configure the sale mode, hard cap and end time before the raise takes deposits
+    pub fn configure_sale(
+        ctx: Context<ConfigureSale>,
+        sale_mode: SaleMode,
+        hard_cap: u64,
+        start_time: i64,
+        end_time: i64,
+    ) -> Result<()> {
+        let project = &mut ctx.accounts.project;
+        require!(
+            project.status == ProjectStatus::Active,
+            OtonomError::ProjectNotActive
+        );
+        require!(!project.has_deposits, OtonomError::SaleAlreadyStarted);
+        // Every sale needs an end time, so it can always be settled
+        require!(
+            end_time > Clock::get()?.unix_timestamp,
+            OtonomError::InvalidSaleConfig
+        );
+        require!(
+            start_time == 0 || start_time < end_time,
+            OtonomError::InvalidSaleConfig
+        );
+
+        // Overflow sales hold deposits in a vault owned by the project PDA so excess can be refunded
+        if sale_mode == SaleMode::Overflow {
+            require!(hard_cap > 0, OtonomError::InvalidSaleConfig);
+            require!(
+                ctx.accounts.project_vault.owner == project.key(),
+                OtonomError::EscrowVaultRequired
+            );
+        }
+
+        project.sale_mode = sale_mode;
+        project.hard_cap = hard_cap;
+        project.start_time = start_time;
+        project.end_time = end_time;
+
+        msg!("Sale configured for project {}", project.name);
+        Ok(())
+    }
+
+    // Set the minimum and maximum single investment for a project (0 max = no limit)
+    pub fn configure_ticket_size(
+        ctx: Context<ConfigureTicketSize>,
+        min_investment: u64,
+        max_investment: u64,
+    ) -> Result<()> {
+        let project = &mut ctx.accounts.project;
+        require!(
+            project.status == ProjectStatus::Active,
+            OtonomError::ProjectNotActive
+        );
+        require!(!project.has_deposits, OtonomError::SaleAlreadyStarted);
+        require!(
+            max_investment == 0 || min_investment <= max_investment,
+            OtonomError::InvalidTicketSize
+        );
+
+        project.min_investment = min_investment;
+        project.max_investment = max_investment;
+
+        msg!(
+            "Ticket size for project {} set to {}..{}",
+            project.name,
+            min_investment,
+            max_investment
+        );
+        Ok(())
+    }
+
+    // Settle one overflow contribution: keep the pro-rata accepted amount and refund the rest
+    pub fn settle_overflow(ctx: Context<SettleOverflow>) -> Result<()> {
+        let project = &ctx.accounts.project;
+        require!(
+            project.sale_mode == SaleMode::Overflow,
+            OtonomError::NotOverflowSale
+        );
+        require!(
+            project.status != ProjectStatus::Cancelled,
+            OtonomError::ProjectCancelled
+        );
+        require!(
+            sale_ended(project, Clock::get()?.unix_timestamp),
+            OtonomError::SaleNotEnded
+        );
+        require!(
+            !ctx.accounts.contribution.settled,
+            OtonomError::ContributionAlreadySettled
+        );
+
+        let deposited = ctx.accounts.contribution.amount;
+        let accepted = overflow_accepted_amount(deposited, project.hard_cap, project.total_raised)?;
+        let refund = deposited
+            .checked_sub(accepted)
+            .ok_or(error!(OtonomError::ArithmeticOverflow))?;
+
+        if refund > 0 {
+            transfer_from_escrow(
+                project,
+                &ctx.accounts.project_vault,
+                &ctx.accounts.payment_mint,
+                &ctx.accounts.investor_token_account,
+                &ctx.accounts.token_program,
+                refund,
+            )?;
+            reduce_invested_totals(
+                &mut ctx.accounts.user_profile,
+                ctx.accounts.program_config.ofund_mint,
+                project.payment_mint,
+                refund,
+                &ctx.accounts.tier_config,
+                Clock::get()?.unix_timestamp,
+            );
+            forfeit_loyalty_points(&mut ctx.accounts.contribution, refund, deposited);
+            if project.payment_mint == ctx.accounts.program_config.ofund_mint {
+                let stats = &mut ctx.accounts.global_stats;
+                stats.total_ofund_raised = stats.total_ofund_raised.saturating_sub(refund);
+            }
+        }
+
+        let contribution = &mut ctx.accounts.contribution;
+        contribution.accepted = accepted;
+        contribution.settled = true;
+
+        // Contributions emptied by a full withdrawal were already uncounted
+        let project = &mut ctx.accounts.project;
+        if deposited > 0 {
+            project.unsettled_contributions = project
+                .unsettled_contributions
+                .checked_sub(1)
+                .ok_or(error!(OtonomError::ArithmeticOverflow))?;
+        }
+        if accepted == 0 && deposited > 0 {
+            project.open_contributions = project.open_contributions.saturating_sub(1);
+        }
+
+        msg!("Overflow settled: {} accepted, {} refunded", accepted, refund);
+        Ok(())
+    }
+
+    // Withdraw raised funds from a project's escrow vault to the authority
+    pub fn withdraw_proceeds(ctx: Context<WithdrawProceeds>, amount: u64) -> Result<()> {
+        let project = &ctx.accounts.project;
+        // Escrow of a cancelled project belongs to its investors
+        require!(
+            project.status != ProjectStatus::Cancelled,
+            OtonomError::ProjectCancelled
+        );
+        require!(
+            project.status == ProjectStatus::Settled
+                || sale_ended(project, Clock::get()?.unix_timestamp),
+            OtonomError::SaleNotEnded
+        );
+
+        // While overflow refunds are outstanding only the hard cap may leave the vault
+        let available = if project.sale_mode == SaleMode::Overflow
+            && project.unsettled_contributions > 0
+        {
+            project
+                .total_raised
+                .min(project.hard_cap)
+                .checked_sub(project.proceeds_withdrawn)
+                .ok_or(error!(OtonomError::ArithmeticOverflow))?
+        } else {
+            ctx.accounts.project_vault.amount
+        };
+        require!(amount <= available, OtonomError::InsufficientProceeds);
+
+        transfer_from_escrow(
+            project,
+            &ctx.accounts.project_vault,
+            &ctx.accounts.payment_mint,
+            &ctx.accounts.authority_token_account,
+            &ctx.accounts.token_program,
+            amount,
+        )?;
+
+        let project = &mut ctx.accounts.project;
+        project.proceeds_withdrawn = project
+            .proceeds_withdrawn
+            .checked_add(amount)
+            .ok_or(error!(OtonomError::ArithmeticOverflow))?;
+
+        msg!("Withdrew {} from project {}", amount, project.name);
+        Ok(())
+    }
+
+    // Require tiers used by this project to be frozen at a snapshot time and held
+    // for a minimum duration before it
+    pub fn configure_tier_snapshot(
+        ctx: Context<ConfigureTierSnapshot>,
+        snapshot_time: i64,
+        min_hold_duration: i64,
+    ) -> Result<()> {
+        let project = &mut ctx.accounts.project;
+        require!(
+            project.status == ProjectStatus::Active,
+            OtonomError::ProjectNotActive
+        );
+        require!(!project.has_deposits, OtonomError::SaleAlreadyStarted);
+        require!(
+            snapshot_time > Clock::get()?.unix_timestamp && min_hold_duration >= 0,
+            OtonomError::InvalidSnapshotConfig
+        );
+
+        project.tier_snapshot_time = snapshot_time;
+        project.min_tier_hold = min_hold_duration;
+
+        msg!(
+            "Tier snapshot for project {} set at {} with {}s minimum hold",
+            project.name,
+            snapshot_time,
+            min_hold_duration
+        );
+        Ok(())
+    }
+
+    // Freeze a user's eligible tier for a project once its snapshot time has passed
+    pub fn snapshot_tier(ctx: Context<SnapshotTier>) -> Result<()> {
+        let project = &ctx.accounts.project;
+        require!(project.tier_snapshot_time != 0, OtonomError::SnapshotNotConfigured);
+        require!(
+            Clock::get()?.unix_timestamp >= project.tier_snapshot_time,
+            OtonomError::SnapshotTimeNotReached
+        );
+
+        let held_since = project
+            .tier_snapshot_time
+            .checked_sub(project.min_tier_hold)
+            .ok_or(error!(OtonomError::ArithmeticOverflow))?;
+        let tier = min_tier_held(
+            &ctx.accounts.user_profile,
+            held_since,
+            project.tier_snapshot_time,
+        );
+
+        let snapshot = &mut ctx.accounts.tier_snapshot;
+        snapshot.bump = ctx.bumps.tier_snapshot;
+        snapshot.project = project.key();
+        snapshot.user = ctx.accounts.user_profile.user;
+        snapshot.tier = tier;
+        snapshot.snapshot_time = project.tier_snapshot_time;
+
+        msg!("Tier {} frozen for {}", tier, snapshot.user);
+        Ok(())
+    }
+
+    // Create the roles account used to delegate admin permissions
+    pub fn initialize_roles(ctx: Context<InitializeRoles>) -> Result<()> {
+        require!(
+            admin_signed(
+                &ctx.accounts.mint_authority,
+                ctx.accounts.admin_multisig.as_ref(),
+                ctx.accounts.admin.key(),
+                ctx.remaining_accounts,
+            ),
+            OtonomError::Unauthorized
+        );
+        let roles = &mut ctx.accounts.roles;
+        roles.bump = ctx.bumps.roles;
+        roles.mint = ctx.accounts.mint_authority.mint;
+        roles.members = Vec::new();
+
+        msg!("Roles initialized");
+        Ok(())
+    }
+
+    // Revoke one or more roles (bitmask) from a member.
+    // Revocation only reduces privileges, so it is not timelocked.
+    pub fn revoke_role(ctx: Context<UpdateRoles>, member: Pubkey, role: u8) -> Result<()> {
+        require!(
+            admin_signed(
+                &ctx.accounts.mint_authority,
+                ctx.accounts.admin_multisig.as_ref(),
+                ctx.accounts.admin.key(),
+                ctx.remaining_accounts,
+            ),
+            OtonomError::Unauthorized
+        );
+        remove_role(&mut ctx.accounts.roles, member, role)
+    }
+
+    // Queue a critical admin change; it can be executed once `eta` has passed
+    pub fn queue_admin_action(
+        ctx: Context<QueueAdminAction>,
+        action_id: u64,
+        action: AdminAction,
+        eta: i64,
+    ) -> Result<()> {
+        require!(
+            is_admin_approver(
+                &ctx.accounts.mint_authority,
+                ctx.accounts.admin_multisig.as_ref(),
+                ctx.accounts.proposer.key(),
+            ),
+            OtonomError::Unauthorized
+        );
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            eta >= now.saturating_add(MIN_TIMELOCK_DELAY),
+            OtonomError::TimelockTooShort
+        );
+        match action {
+            AdminAction::GrantRole { role, .. } | AdminAction::RevokeRole { role, .. } => {
+                require!(
+                    role != 0 && role & !ALL_ROLES == 0,
+                    OtonomError::InvalidRole
+                );
+            }
+            AdminAction::UpdateRegistrationLimits { window_duration, .. } => {
+                require!(window_duration > 0, OtonomError::InvalidRegistrationLimits);
+            }
+            // The level's place in the table is checked on execution, against
+            // the table as it stands then
+            AdminAction::AddTierLevel { ref level }
+            | AdminAction::UpdateTierLevel { ref level, .. } => validate_tier_level(level)?,
+            AdminAction::UpdatePointsConfig { ref rates, .. } => validate_points_rates(rates)?,
+            _ => {}
+        }
+
+        let pending_action = &mut ctx.accounts.pending_action;
+        pending_action.bump = ctx.bumps.pending_action;
+        pending_action.mint = ctx.accounts.mint_authority.mint;
+        pending_action.action_id = action_id;
+        pending_action.proposer = ctx.accounts.proposer.key();
+        pending_action.action = action;
+        pending_action.queued_at = now;
+        pending_action.eta = eta;
+        pending_action.approvals = vec![ctx.accounts.proposer.key()];
+
+        msg!("Admin action {} queued, executable at {}", action_id, eta);
+        Ok(())
+    }
+
+    // Execute a queued admin change after its timelock has elapsed
+    pub fn execute_admin_action(ctx: Context<ExecuteAdminAction>) -> Result<()> {
+        let pending_action = &ctx.accounts.pending_action;
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= pending_action.eta, OtonomError::TimelockNotElapsed);
+        require!(
+            now <= pending_action.eta.saturating_add(TIMELOCK_GRACE_PERIOD),
+            OtonomError::TimelockExpired
+        );
+
+        // The current admin must have approved: directly, or via m-of-n when the
+        // admin is an on-chain multisig
+        let mint_authority = &ctx.accounts.mint_authority;
+        let approved = match ctx.accounts.admin_multisig.as_ref() {
+            Some(multisig) if multisig.key() == mint_authority.admin => {
+                let count = pending_action
+                    .approvals
+                    .iter()
+                    .filter(|approver| multisig.signers.contains(approver))
+                    .count();
+                count >= multisig.threshold as usize
+            }
+            _ => pending_action.approvals.contains(&mint_authority.admin),
+        };
+        require!(approved, OtonomError::InsufficientApprovals);
+
+        match pending_action.action.clone() {
+            AdminAction::TransferAdmin { new_admin } => {
+                ctx.accounts.mint_authority.admin = new_admin;
+                msg!("Admin transferred to {}", new_admin);
+            }
+            AdminAction::GrantRole { member, role } => {
+                let roles = ctx
+                    .accounts
+                    .roles
+                    .as_mut()
+                    .ok_or(error!(OtonomError::RolesAccountRequired))?;
+                add_role(roles, member, role)?;
+            }
+            AdminAction::UpdateRegistrationLimits {
+                window_duration,
+                max_registrations_per_window,
+                registration_fee,
+                treasury,
+            } => {
+                let limiter = ctx
+                    .accounts
+                    .registration_limiter
+                    .as_mut()
+                    .ok_or(error!(OtonomError::RegistrationLimiterRequired))?;
+                limiter.window_duration = window_duration;
+                limiter.max_registrations_per_window = max_registrations_per_window;
+                limiter.registration_fee = registration_fee;
+                limiter.treasury = treasury;
+                msg!("Registration limits updated");
+            }
+            AdminAction::SetAttester { attester } => {
+                let kyc_config = ctx
+                    .accounts
+                    .kyc_config
+                    .as_mut()
+                    .ok_or(error!(OtonomError::KycConfigRequired))?;
+                kyc_config.attester = attester;
+                msg!("KYC attester set to {}", attester);
+            }
+            AdminAction::RevokeRole { member, role } => {
+                let roles = ctx
+                    .accounts
+                    .roles
+                    .as_mut()
+                    .ok_or(error!(OtonomError::RolesAccountRequired))?;
+                remove_role(roles, member, role)?;
+            }
+            AdminAction::AddTierLevel { level } => {
+                let tier_config = ctx
+                    .accounts
+                    .tier_config
+                    .as_mut()
+                    .ok_or(error!(OtonomError::TierConfigRequired))?;
+                push_tier_level(tier_config, level)?;
+            }
+            AdminAction::UpdateTierLevel { index, level } => {
+                let tier_config = ctx
+                    .accounts
+                    .tier_config
+                    .as_mut()
+                    .ok_or(error!(OtonomError::TierConfigRequired))?;
+                replace_tier_level(tier_config, index, level)?;
+            }
+            AdminAction::RemoveTierLevel => {
+                let tier_config = ctx
+                    .accounts
+                    .tier_config
+                    .as_mut()
+                    .ok_or(error!(OtonomError::TierConfigRequired))?;
+                pop_tier_level(tier_config)?;
+            }
+            // Points already accrued, pending or claimed, are unaffected
+            AdminAction::UpdatePointsConfig {
+                rates,
+                points_per_lottery_ticket,
+                max_bonus_tickets,
+            } => {
+                let points_config = ctx
+                    .accounts
+                    .points_config
+                    .as_mut()
+                    .ok_or(error!(OtonomError::PointsConfigRequired))?;
+                points_config.rates = rates;
+                points_config.points_per_lottery_ticket = points_per_lottery_ticket;
+                points_config.max_bonus_tickets = max_bonus_tickets;
+                msg!("Points config updated with {} rates", points_config.rates.len());
+            }
+        }
+
+        msg!("Admin action {} executed", pending_action.action_id);
+        Ok(())
+    }
+
+    // Approve a queued admin change as a member of the admin multisig
+    pub fn approve_admin_action(ctx: Context<ApproveAdminAction>) -> Result<()> {
+        require!(
+            is_admin_approver(
+                &ctx.accounts.mint_authority,
+                Some(&ctx.accounts.admin_multisig),
+                ctx.accounts.approver.key(),
+            ),
+            OtonomError::Unauthorized
+        );
+
+        let approver = ctx.accounts.approver.key();
+        let pending_action = &mut ctx.accounts.pending_action;
+        require!(
+            !pending_action.approvals.contains(&approver),
+            OtonomError::AlreadyApproved
+        );
+        require!(
+            pending_action.approvals.len() < MAX_MULTISIG_SIGNERS,
+            OtonomError::InsufficientApprovals
+        );
+        pending_action.approvals.push(approver);
+
+        msg!(
+            "Admin action {} approved by {} ({} approvals)",
+            pending_action.action_id,
+            approver,
+            pending_action.approvals.len()
+        );
+        Ok(())
+    }
+
+    // Cancel a queued admin change
+    pub fn cancel_admin_action(ctx: Context<CancelAdminAction>) -> Result<()> {
+        require!(
+            is_admin_approver(
+                &ctx.accounts.mint_authority,
+                ctx.accounts.admin_multisig.as_ref(),
+                ctx.accounts.canceller.key(),
+            ),
+            OtonomError::Unauthorized
+        );
+        msg!(
+            "Admin action {} cancelled",
+            ctx.accounts.pending_action.action_id
+        );
+        Ok(())
+    }
+
+    // Create an on-chain m-of-n multisig. Transfer the admin to its address through
+    // the timelock to switch admin actions to multisig approval.
+    pub fn initialize_admin_multisig(
+        ctx: Context<InitializeAdminMultisig>,
+        create_key: Pubkey,
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        require!(
+            admin_signed(
+                &ctx.accounts.mint_authority,
+                ctx.accounts.current_admin_multisig.as_ref(),
+                ctx.accounts.admin.key(),
+                ctx.remaining_accounts,
+            ),
+            OtonomError::Unauthorized
+        );
+        require!(
+            !signers.is_empty()
+                && signers.len() <= MAX_MULTISIG_SIGNERS
+                && threshold > 0
+                && threshold as usize <= signers.len(),
+            OtonomError::InvalidMultisigConfig
+        );
+        for (i, signer) in signers.iter().enumerate() {
+            require!(
+                !signers[..i].contains(signer),
+                OtonomError::InvalidMultisigConfig
+            );
+        }
+
+        let multisig = &mut ctx.accounts.admin_multisig;
+        multisig.bump = ctx.bumps.admin_multisig;
+        multisig.mint = ctx.accounts.mint_authority.mint;
+        multisig.create_key = create_key;
+        multisig.signers = signers;
+        multisig.threshold = threshold;
+
+        msg!(
+            "Admin multisig {} created ({} of {})",
+            multisig.key(),
+            threshold,
+            multisig.signers.len()
+        );
+        Ok(())
+    }
+
+    // Create the KYC config holding the key allowed to issue attestations
+    pub fn initialize_kyc_config(ctx: Context<InitializeKycConfig>, attester: Pubkey) -> Result<()> {
+        require!(
+            admin_signed(
+                &ctx.accounts.mint_authority,
+                ctx.accounts.admin_multisig.as_ref(),
+                ctx.accounts.admin.key(),
+                ctx.remaining_accounts,
+            ),
+            OtonomError::Unauthorized
+        );
+        let kyc_config = &mut ctx.accounts.kyc_config;
+        kyc_config.bump = ctx.bumps.kyc_config;
+        kyc_config.mint = ctx.accounts.mint_authority.mint;
+        kyc_config.attester = attester;
+
+        msg!("KYC attester set to {}", attester);
+        Ok(())
+    }
+
+    // Issue or renew a user's KYC attestation
+    pub fn issue_attestation(
+        ctx: Context<IssueAttestation>,
+        user: Pubkey,
+        expires_at: i64,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(expires_at > now, OtonomError::AttestationExpired);
+
+        let attestation = &mut ctx.accounts.attestation;
+        attestation.bump = ctx.bumps.attestation;
+        attestation.mint = ctx.accounts.kyc_config.mint;
+        attestation.user = user;
+        attestation.attester = ctx.accounts.attester.key();
+        attestation.issued_at = now;
+        attestation.expires_at = expires_at;
+        attestation.revoked = false;
+
+        msg!("Attestation issued for {} until {}", user, expires_at);
+        Ok(())
+    }
+
+    // Revoke a user's KYC attestation
+    pub fn revoke_attestation(ctx: Context<RevokeAttestation>) -> Result<()> {
+        let attestation = &mut ctx.accounts.attestation;
+        attestation.revoked = true;
+
+        msg!("Attestation revoked for {}", attestation.user);
+        Ok(())
+    }
+
+    // Toggle whether investing in a project requires a KYC attestation. Locked once
+    // the raise opens, so investors are held to the terms the sale started with.
+    pub fn set_kyc_requirement(ctx: Context<SetKycRequirement>, requires_kyc: bool) -> Result<()> {
+        let project = &mut ctx.accounts.project;
+        require!(
+            !raise_started(project, Clock::get()?.unix_timestamp),
+            OtonomError::SaleAlreadyStarted
+        );
+        project.requires_kyc = requires_kyc;
+
+        msg!("Project {} requires KYC: {}", project.name, requires_kyc);
+        Ok(())
+    }
+
+    // Block a wallet from registering and investing
+    pub fn add_to_blocklist(ctx: Context<AddToBlocklist>, wallet: Pubkey) -> Result<()> {
+        require!(
+            admin_signed(
+                &ctx.accounts.mint_authority,
+                ctx.accounts.admin_multisig.as_ref(),
+                ctx.accounts.admin.key(),
+                ctx.remaining_accounts,
+            ),
+            OtonomError::Unauthorized
+        );
+        let entry = &mut ctx.accounts.blocklist_entry;
+        entry.bump = ctx.bumps.blocklist_entry;
+        entry.mint = ctx.accounts.mint_authority.mint;
+        entry.wallet = wallet;
+        entry.added_at = Clock::get()?.unix_timestamp;
+
+        emit!(BlocklistAdded {
+            mint: entry.mint,
+            wallet,
+            timestamp: entry.added_at,
+        });
+        msg!("Wallet {} added to blocklist", wallet);
+        Ok(())
+    }
+
+    // Unblock a wallet, closing its blocklist entry
+    pub fn remove_from_blocklist(ctx: Context<RemoveFromBlocklist>) -> Result<()> {
+        require!(
+            admin_signed(
+                &ctx.accounts.mint_authority,
+                ctx.accounts.admin_multisig.as_ref(),
+                ctx.accounts.admin.key(),
+                ctx.remaining_accounts,
+            ),
+            OtonomError::Unauthorized
+        );
+        let entry = &ctx.accounts.blocklist_entry;
+
+        emit!(BlocklistRemoved {
+            mint: entry.mint,
+            wallet: entry.wallet,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg!("Wallet {} removed from blocklist", entry.wallet);
+        Ok(())
+    }
+
+    // Create the registration limiter with a per-window cap and optional SOL fee.
+    // Later changes go through the timelock (AdminAction::UpdateRegistrationLimits).
+    pub fn initialize_registration_limiter(
+        ctx: Context<InitializeRegistrationLimiter>,
+        window_duration: i64,
+        max_registrations_per_window: u32,
+        registration_fee: u64,
+        treasury: Pubkey,
+    ) -> Result<()> {
+        require!(
+            admin_signed(
+                &ctx.accounts.mint_authority,
+                ctx.accounts.admin_multisig.as_ref(),
+                ctx.accounts.admin.key(),
+                ctx.remaining_accounts,
+            ),
+            OtonomError::Unauthorized
+        );
+        require!(window_duration > 0, OtonomError::InvalidRegistrationLimits);
+
+        let limiter = &mut ctx.accounts.registration_limiter;
+        limiter.bump = ctx.bumps.registration_limiter;
+        limiter.mint = ctx.accounts.mint_authority.mint;
+        limiter.window_duration = window_duration;
+        limiter.max_registrations_per_window = max_registrations_per_window;
+        limiter.registration_fee = registration_fee;
+        limiter.treasury = treasury;
+        limiter.window_start = Clock::get()?.unix_timestamp;
+        limiter.registrations_in_window = 0;
+
+        msg!(
+            "Registration limiter set: {} per {}s, fee {} lamports",
+            max_registrations_per_window,
+            window_duration,
+            registration_fee
+        );
+        Ok(())
+    }
+
+    // Create the global statistics account
+    pub fn initialize_global_stats(ctx: Context<InitializeGlobalStats>) -> Result<()> {
+        require!(
+            admin_signed(
+                &ctx.accounts.mint_authority,
+                ctx.accounts.admin_multisig.as_ref(),
+                ctx.accounts.admin.key(),
+                ctx.remaining_accounts,
+            ),
+            OtonomError::Unauthorized
+        );
+        let stats = &mut ctx.accounts.global_stats;
+        stats.bump = ctx.bumps.global_stats;
+        stats.mint = ctx.accounts.mint_authority.mint;
+        stats.user_count = 0;
+        stats.project_count = 0;
+        stats.total_ofund_raised = 0;
+        stats.total_granted = 0;
+        stats.total_fees = 0;
+        stats.total_ofund_penalties = 0;
+
+        msg!("Global stats initialized");
+        Ok(())
+    }
+
+    // Update a project's descriptive metadata; locked once the raise opens
+    pub fn update_project_metadata(
+        ctx: Context<UpdateProjectMetadata>,
+        metadata: ProjectMetadata,
+    ) -> Result<()> {
+        let signer = ctx.accounts.authority.key();
+        require!(
+            is_project_owner(&ctx.accounts.project, signer)
+                || has_role(
+                    &ctx.accounts.mint_authority,
+                    ctx.accounts.roles.as_deref(),
+                    signer,
+                    ROLE_METADATA_MANAGER,
+                ),
+            OtonomError::Unauthorized
+        );
+
+        let project = &mut ctx.accounts.project;
+        require!(
+            !raise_started(project, Clock::get()?.unix_timestamp),
+            OtonomError::MetadataLocked
+        );
+        require!(
+            metadata.uri.len() <= MAX_METADATA_URI_LEN
+                && metadata.website.len() <= MAX_WEBSITE_LEN
+                && metadata.twitter.len() <= MAX_SOCIAL_LEN
+                && metadata.telegram.len() <= MAX_SOCIAL_LEN
+                && metadata.discord.len() <= MAX_SOCIAL_LEN,
+            OtonomError::MetadataTooLong
+        );
+
+        project.metadata = metadata;
+
+        msg!("Metadata updated for project {}", project.name);
+        Ok(())
+    }
+
+    // Reserve a project's display name so no other project can claim it.
+    // The owner pays for the reservation and a curator (or the admin) approves it.
+    pub fn claim_project_name(ctx: Context<ClaimProjectName>) -> Result<()> {
+        require!(
+            has_role(
+                &ctx.accounts.mint_authority,
+                ctx.accounts.roles.as_deref(),
+                ctx.accounts.curator.key(),
+                ROLE_PROJECT_CURATOR,
+            ),
+            OtonomError::Unauthorized
+        );
+
+        let name_record = &mut ctx.accounts.project_name;
+        name_record.bump = ctx.bumps.project_name;
+        name_record.project = ctx.accounts.project.key();
+
+        msg!(
+            "Name '{}' reserved for project {}",
+            ctx.accounts.project.name,
+            ctx.accounts.project.id
+        );
+        Ok(())
+    }
+
+    // Propose a new project owner; takes effect once they accept
+    pub fn transfer_project_authority(
+        ctx: Context<UpdateProjectOwnership>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        let project = &mut ctx.accounts.project;
+        project.pending_authority = Some(new_authority);
+
+        msg!("Project {} ownership offered to {}", project.id, new_authority);
+        Ok(())
+    }
+
+    // Withdraw a pending ownership offer before it is accepted
+    pub fn cancel_project_authority_transfer(ctx: Context<UpdateProjectOwnership>) -> Result<()> {
+        let project = &mut ctx.accounts.project;
+        require!(
+            project.pending_authority.is_some(),
+            OtonomError::NoPendingAuthority
+        );
+        project.pending_authority = None;
+
+        msg!("Project {} ownership offer withdrawn", project.id);
+        Ok(())
+    }
+
+    // Accept a pending project ownership transfer
+    pub fn accept_project_authority(ctx: Context<AcceptProjectAuthority>) -> Result<()> {
+        let project = &mut ctx.accounts.project;
+        let new_authority = ctx.accounts.new_authority.key();
+        project.authority = new_authority;
+        project.pending_authority = None;
+        // The owner already has every co-owner permission
+        project.co_owners.retain(|co_owner| *co_owner != new_authority);
+
+        msg!("Project {} ownership transferred to {}", project.id, new_authority);
+        Ok(())
+    }
+
+    // Allow another key to manage the project's metadata
+    pub fn add_project_co_owner(ctx: Context<UpdateProjectOwnership>, co_owner: Pubkey) -> Result<()> {
+        let project = &mut ctx.accounts.project;
+        require!(
+            co_owner != project.authority && !project.co_owners.contains(&co_owner),
+            OtonomError::AlreadyCoOwner
+        );
+        require!(project.co_owners.len() < MAX_CO_OWNERS, OtonomError::TooManyCoOwners);
+        project.co_owners.push(co_owner);
+
+        msg!("Co-owner {} added to project {}", co_owner, project.id);
+        Ok(())
+    }
+
+    // Revoke a co-owner's metadata permissions
+    pub fn remove_project_co_owner(
+        ctx: Context<UpdateProjectOwnership>,
+        co_owner: Pubkey,
+    ) -> Result<()> {
+        let project = &mut ctx.accounts.project;
+        let position = project
+            .co_owners
+            .iter()
+            .position(|key| *key == co_owner)
+            .ok_or(OtonomError::NotCoOwner)?;
+        project.co_owners.remove(position);
+
+        msg!("Co-owner {} removed from project {}", co_owner, project.id);
+        Ok(())
+    }
+
+    // Abort a listing: block further investment and open escrow refunds.
+    // Callable by the project owner, the admin or a project curator.
+    pub fn cancel_project(ctx: Context<CancelProject>) -> Result<()> {
+        let signer = ctx.accounts.authority.key();
+        require!(
+            signer == ctx.accounts.project.authority
+                || has_role(
+                    &ctx.accounts.mint_authority,
+                    ctx.accounts.roles.as_deref(),
+                    signer,
+                    ROLE_PROJECT_CURATOR,
+                ),
+            OtonomError::Unauthorized
+        );
+
+        let project = &mut ctx.accounts.project;
+        require!(
+            project.status == ProjectStatus::Active,
+            OtonomError::ProjectNotActive
+        );
+        // Proceeds already taken out of escrow could not be refunded
+        require!(
+            project.proceeds_withdrawn == 0,
+            OtonomError::ProceedsAlreadyWithdrawn
+        );
+        require!(
+            ctx.accounts.project_vault.owner == project.key(),
+            OtonomError::EscrowVaultRequired
+        );
+        project.status = ProjectStatus::Cancelled;
+
+        msg!("Project {} cancelled by {}", project.name, signer);
+        Ok(())
+    }
+
+    // Pause or resume deposits into a project. Withdrawals, refunds and settlement
+    // are unaffected, so investors can always leave while a sale is paused.
+    pub fn set_project_paused(ctx: Context<SetProjectPaused>, paused: bool) -> Result<()> {
+        let signer = ctx.accounts.authority.key();
+        require!(
+            has_role(
+                &ctx.accounts.mint_authority,
+                ctx.accounts.roles.as_deref(),
+                signer,
+                ROLE_PAUSER,
+            ),
+            OtonomError::Unauthorized
+        );
+
+        let project = &mut ctx.accounts.project;
+        require!(
+            project.status == ProjectStatus::Active,
+            OtonomError::ProjectNotActive
+        );
+        project.paused = paused;
+
+        msg!("Project {} paused: {} by {}", project.name, paused, signer);
+        Ok(())
+    }
+
+    // Return an investor's full contribution from a cancelled project's escrow
+    pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
+        let project = &ctx.accounts.project;
+        require!(
+            project.status == ProjectStatus::Cancelled,
+            OtonomError::ProjectNotCancelled
+        );
+        require!(
+            !ctx.accounts.contribution.refunded,
+            OtonomError::AlreadyRefunded
+        );
+
+        // Overflow excess already returned by settle_overflow is not refunded twice
+        let refund = refund_due(&ctx.accounts.contribution);
+
+        if refund > 0 {
+            transfer_from_escrow(
+                project,
+                &ctx.accounts.project_vault,
+                &ctx.accounts.payment_mint,
+                &ctx.accounts.investor_token_account,
+                &ctx.accounts.token_program,
+                refund,
+            )?;
+            reduce_invested_totals(
+                &mut ctx.accounts.user_profile,
+                ctx.accounts.program_config.ofund_mint,
+                project.payment_mint,
+                refund,
+                &ctx.accounts.tier_config,
+                Clock::get()?.unix_timestamp,
+            );
+            forfeit_loyalty_points(&mut ctx.accounts.contribution, refund, refund);
+            if project.payment_mint == ctx.accounts.program_config.ofund_mint {
+                let stats = &mut ctx.accounts.global_stats;
+                stats.total_ofund_raised = stats.total_ofund_raised.saturating_sub(refund);
+            }
+        }
+
+        ctx.accounts.contribution.refunded = true;
+
+        let project = &mut ctx.accounts.project;
+        project.total_raised = project.total_raised.saturating_sub(refund);
+        if refund > 0 {
+            project.open_contributions = project.open_contributions.saturating_sub(1);
+        }
+
+        msg!("Refunded {} from cancelled project {}", refund, project.name);
+        Ok(())
+    }
+
+    // Set the penalty charged on early withdrawals and the token account that receives it
+    pub fn configure_exit_penalty(
+        ctx: Context<ConfigureExitPenalty>,
+        penalty_bps: u16,
+        treasury: Pubkey,
+    ) -> Result<()> {
+        require!(
+            has_role(
+                &ctx.accounts.mint_authority,
+                ctx.accounts.roles.as_deref(),
+                ctx.accounts.fee_manager.key(),
+                ROLE_FEE_MANAGER,
+            ),
+            OtonomError::Unauthorized
+        );
+        require!(
+            u64::from(penalty_bps) <= BPS_DENOMINATOR,
+            OtonomError::InvalidPenalty
+        );
+
+        // Investors join knowing the penalty, so it is fixed once the raise opens
+        let project = &mut ctx.accounts.project;
+        require!(
+            project.status == ProjectStatus::Active,
+            OtonomError::ProjectNotActive
+        );
+        require!(
+            !raise_started(project, Clock::get()?.unix_timestamp),
+            OtonomError::SaleAlreadyStarted
+        );
+        project.exit_penalty_bps = penalty_bps;
+        project.exit_penalty_treasury = treasury;
+
+        msg!("Exit penalty for project {} set to {} bps", project.name, penalty_bps);
+        Ok(())
+    }
+
+    // Withdraw part or all of a contribution while the raise is still open
+    pub fn withdraw_investment(ctx: Context<WithdrawInvestment>, amount: u64) -> Result<()> {
+        require!(amount > 0, OtonomError::ZeroInvestment);
+        let now = Clock::get()?.unix_timestamp;
+        let project = &ctx.accounts.project;
+        require!(
+            project.status == ProjectStatus::Active,
+            OtonomError::ProjectNotActive
+        );
+        require!(raise_started(project, now), OtonomError::SaleNotStarted);
+        require!(!sale_ended(project, now), OtonomError::SaleEnded);
+        require!(
+            ctx.accounts.project_vault.owner == project.key(),
+            OtonomError::EscrowVaultRequired
+        );
+        require!(
+            amount <= ctx.accounts.contribution.amount,
+            OtonomError::InsufficientContribution
+        );
+
+        let penalty = (u128::from(amount) * u128::from(project.exit_penalty_bps)
+            / u128::from(BPS_DENOMINATOR)) as u64;
+        let payout = amount
+            .checked_sub(penalty)
+            .ok_or(error!(OtonomError::ArithmeticOverflow))?;
+
+        if penalty > 0 {
+            let treasury = ctx
+                .accounts
+                .treasury_token_account
+                .as_ref()
+                .ok_or(error!(OtonomError::TreasuryRequired))?;
+            transfer_from_escrow(
+                project,
+                &ctx.accounts.project_vault,
+                &ctx.accounts.payment_mint,
+                treasury,
+                &ctx.accounts.token_program,
+                penalty,
+            )?;
+        }
+        if payout > 0 {
+            transfer_from_escrow(
+                project,
+                &ctx.accounts.project_vault,
+                &ctx.accounts.payment_mint,
+                &ctx.accounts.investor_token_account,
+                &ctx.accounts.token_program,
+                payout,
+            )?;
+        }
+
+        // Free up the lottery allocation the withdrawn amount was using
+        if let Some(ticket) = ctx.accounts.lottery_ticket.as_mut() {
+            ticket.invested = ticket.invested.saturating_sub(amount);
+        }
+
+        let user_profile = &mut ctx.accounts.user_profile;
+        reduce_invested_totals(
+            user_profile,
+            ctx.accounts.program_config.ofund_mint,
+            project.payment_mint,
+            amount,
+            &ctx.accounts.tier_config,
+            now,
+        );
+        let deposited = ctx.accounts.contribution.amount;
+        forfeit_loyalty_points(&mut ctx.accounts.contribution, amount, deposited);
+        if project.payment_mint == ctx.accounts.program_config.ofund_mint {
+            let stats = &mut ctx.accounts.global_stats;
+            stats.total_ofund_raised = stats.total_ofund_raised.saturating_sub(amount);
+            stats.total_ofund_penalties = stats
+                .total_ofund_penalties
+                .checked_add(penalty)
+                .ok_or(error!(OtonomError::ArithmeticOverflow))?;
+        }
+
+        let contribution = &mut ctx.accounts.contribution;
+        contribution.amount = contribution
+            .amount
+            .checked_sub(amount)
+            .ok_or(error!(OtonomError::ArithmeticOverflow))?;
+
+        let project = &mut ctx.accounts.project;
+        project.total_raised = project
+            .total_raised
+            .checked_sub(amount)
+            .ok_or(error!(OtonomError::ArithmeticOverflow))?;
+        if ctx.accounts.contribution.amount == 0 {
+            project.open_contributions = project.open_contributions.saturating_sub(1);
+            if project.sale_mode == SaleMode::Overflow {
+                project.unsettled_contributions = project
+                    .unsettled_contributions
+                    .checked_sub(1)
+                    .ok_or(error!(OtonomError::ArithmeticOverflow))?;
+            }
+        }
+
+        msg!(
+            "Withdrew {} from project {} ({} penalty)",
+            amount,
+            project.name,
+            penalty
+        );
+        Ok(())
+    }
+
+    // Recompute a user's tier from their current qualifying total. Permissionless.
+    pub fn refresh_tier(ctx: Context<RefreshTier>) -> Result<()> {
+        let user_profile = &mut ctx.accounts.user_profile;
+        update_tier(user_profile, &ctx.accounts.tier_config, Clock::get()?.unix_timestamp);
+
+        msg!("Tier for {} is {}", user_profile.user, user_profile.tier);
+        Ok(())
+    }
+
+    // Set per-tier allocation weights and early-access delays, indexed by tier, and
+    // the base allocation the weights scale (0 = no limit). Tiers beyond the list
+    // use its last entry. Pass an empty list to clear them.
+    pub fn configure_tier_benefits(
+        ctx: Context<ConfigureTierBenefits>,
+        benefits: Vec<TierBenefit>,
+        max_allocation: u64,
+    ) -> Result<()> {
+        let project = &mut ctx.accounts.project;
+        require!(
+            project.status == ProjectStatus::Active,
+            OtonomError::ProjectNotActive
+        );
+        require!(!project.has_deposits, OtonomError::SaleAlreadyStarted);
+        require!(
+            benefits.len() <= MAX_TIER_BENEFITS,
+            OtonomError::InvalidTierBenefits
+        );
+        for (i, benefit) in benefits.iter().enumerate() {
+            require!(
+                benefit.weight_bps > 0 && benefit.access_delay >= 0,
+                OtonomError::InvalidTierBenefits
+            );
+            // Higher tiers must never open later than lower ones
+            if i > 0 {
+                require!(
+                    benefit.access_delay <= benefits[i - 1].access_delay,
+                    OtonomError::InvalidTierBenefits
+                );
+            }
+        }
+        // Delays count from the sale start, so one must be set
+        require!(
+            project.start_time != 0 || benefits.iter().all(|b| b.access_delay == 0),
+            OtonomError::InvalidTierBenefits
+        );
+
+        project.tier_benefits = benefits;
+        project.max_allocation = max_allocation;
+
+        msg!("Tier benefits configured for project {}", project.name);
+        Ok(())
+    }
+
+    // Name the canonical OFUND mint. User profiles are shared by every mint, so
+    // tiers are only ever computed from this mint's tier table. Set once, by the
+    // program's upgrade authority.
+    pub fn initialize_program_config(ctx: Context<InitializeProgramConfig>) -> Result<()> {
+        let program_config = &mut ctx.accounts.program_config;
+        program_config.bump = ctx.bumps.program_config;
+        program_config.ofund_mint = ctx.accounts.mint_authority.mint;
+
+        msg!("OFUND mint set to {}", program_config.ofund_mint);
+        Ok(())
+    }
+
+    // Create the tier table, seeded with the original four levels
+    pub fn initialize_tier_config(ctx: Context<InitializeTierConfig>) -> Result<()> {
+        require!(
+            admin_signed(
+                &ctx.accounts.mint_authority,
+                ctx.accounts.admin_multisig.as_ref(),
+                ctx.accounts.admin.key(),
+                ctx.remaining_accounts,
+            ),
+            OtonomError::Unauthorized
+        );
+        let tier_config = &mut ctx.accounts.tier_config;
+        tier_config.bump = ctx.bumps.tier_config;
+        tier_config.mint = ctx.accounts.mint_authority.mint;
+        tier_config.tiers = vec![
+            TierLevel {
+                threshold: 0,
+                name: "Member".to_string(),
+                lottery_tickets: 1,
+            },
+            TierLevel {
+                threshold: 1_000 * 10u64.pow(9),
+                name: "Bronze".to_string(),
+                lottery_tickets: 2,
+            },
+            TierLevel {
+                threshold: 10_000 * 10u64.pow(9),
+                name: "Silver".to_string(),
+                lottery_tickets: 3,
+            },
+            TierLevel {
+                threshold: 100_000 * 10u64.pow(9),
+                name: "Gold".to_string(),
+                lottery_tickets: 4,
+            },
+        ];
+
+        msg!("Tier config initialized");
+        Ok(())
+    }
+
+    // Create the loyalty points config with its accrual and lottery rates
+    pub fn initialize_points_config(
+        ctx: Context<InitializePointsConfig>,
+        rates: Vec<PointsRate>,
+        points_per_lottery_ticket: u64,
+        max_bonus_tickets: u64,
+    ) -> Result<()> {
+        require!(
+            admin_signed(
+                &ctx.accounts.mint_authority,
+                ctx.accounts.admin_multisig.as_ref(),
+                ctx.accounts.admin.key(),
+                ctx.remaining_accounts,
+            ),
+            OtonomError::Unauthorized
+        );
+        validate_points_rates(&rates)?;
+        let points_config = &mut ctx.accounts.points_config;
+        points_config.bump = ctx.bumps.points_config;
+        points_config.mint = ctx.accounts.mint_authority.mint;
+        points_config.rates = rates;
+        points_config.points_per_lottery_ticket = points_per_lottery_ticket;
+        points_config.max_bonus_tickets = max_bonus_tickets;
+
+        msg!("Points config initialized with {} rates", points_config.rates.len());
+        Ok(())
+    }
+
+    // Credit a contribution's pending loyalty points to its investor once the
+    // project has settled, when the deposit can no longer leave escrow. Permissionless.
+    pub fn claim_loyalty_points(ctx: Context<ClaimLoyaltyPoints>) -> Result<()> {
+        match project_status(&ctx.accounts.project)? {
+            None | Some(ProjectStatus::Settled) => {}
+            Some(_) => return err!(OtonomError::ProjectNotSettled),
+        }
+
+        let contribution = &mut ctx.accounts.contribution;
+        let points = contribution.points_earned;
+        contribution.points_earned = 0;
+        let user_profile = &mut ctx.accounts.user_profile;
+        user_profile.loyalty_points = user_profile
+            .loyalty_points
+            .checked_add(points)
+            .ok_or(error!(OtonomError::ArithmeticOverflow))?;
+
+        msg!("{} loyalty points credited to {}", points, contribution.user);
+        Ok(())
+    }
+
+    // Close a contribution once its project is settled or closed, or its refund
+    // has been paid, returning rent to the investor. Unclaimed loyalty points are
+    // forfeited.
+    pub fn close_contribution(ctx: Context<CloseContribution>) -> Result<()> {
+        match project_status(&ctx.accounts.project)? {
+            None | Some(ProjectStatus::Settled) => {}
+            Some(ProjectStatus::Cancelled) => require!(
+                refund_due(&ctx.accounts.contribution) == 0,
+                OtonomError::RefundUnclaimed
+            ),
+            Some(ProjectStatus::Active) => return err!(OtonomError::OpenPositionsRemain),
+        }
+
+        msg!("Contribution closed for {}", ctx.accounts.user.key());
+        Ok(())
+    }
+
+    // Close a lottery ticket once its project is no longer raising
+    pub fn close_lottery_ticket(ctx: Context<CloseLotteryTicket>) -> Result<()> {
+        require!(
+            !has_open_position(&ctx.accounts.project)?,
+            OtonomError::OpenPositionsRemain
+        );
+
+        msg!("Lottery ticket closed for {}", ctx.accounts.user.key());
+        Ok(())
+    }
+
+    // Close a tier snapshot once its project is no longer raising
+    pub fn close_tier_snapshot(ctx: Context<CloseTierSnapshot>) -> Result<()> {
+        require!(
+            !has_open_position(&ctx.accounts.project)?,
+            OtonomError::OpenPositionsRemain
+        );
+
+        msg!("Tier snapshot closed for {}", ctx.accounts.user.key());
+        Ok(())
+    }
+}
+
+// Calculate tier based on token balance: the highest level whose threshold the
+// balance reaches. Levels are sorted by threshold and the first starts at 0.
+fn calculate_tier(tier_config: &TierConfig, balance: u64) -> u8 {
+    let reached = tier_config
+        .tiers
+        .partition_point(|level| level.threshold <= balance);
+    reached.saturating_sub(1) as u8
+}
+
+// Verify a Merkle proof using sorted-pair keccak hashing
+fn verify_merkle_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    let mut computed = leaf;
+    for node in proof {
+        computed = if computed <= *node {
+            keccak::hashv(&[&computed, node]).0
+        } else {
+            keccak::hashv(&[node, &computed]).0
+        };
+    }
+    computed == root
+}
+
+// Airdrop Merkle leaf. The layout must match the off-chain tree builder:
+// index || claimant || amount
+fn airdrop_leaf(index: u64, claimant: &Pubkey, amount: u64) -> [u8; 32] {
+    keccak::hashv(&[&index.to_le_bytes(), claimant.as_ref(), &amount.to_le_bytes()]).0
+}
+
+// Number of bytes needed to track `num_nodes` claims as a bitmap
+fn bitmap_len(num_nodes: u64) -> usize {
+    num_nodes.div_ceil(8) as usize
+}
+
+// Status of the given project account, or None once it has been closed
+// (no longer owned by this program)
+fn project_status(project_info: &AccountInfo) -> Result<Option<ProjectStatus>> {
+    if project_info.owner != &crate::ID || project_info.data_is_empty() {
+        return Ok(None);
+    }
+    let project = Project::try_deserialize(&mut &project_info.try_borrow_data()?[..])?;
+    Ok(Some(project.status))
+}
+
+// Whether an investment in the given project account is still open.
+// Closed project accounts count as finished.
+fn has_open_position(project_info: &AccountInfo) -> Result<bool> {
+    Ok(project_status(project_info)? == Some(ProjectStatus::Active))
+}
+
+// Amount claim_refund would return for a contribution if its project is cancelled
+fn refund_due(contribution: &Contribution) -> u64 {
+    if contribution.refunded {
+        0
+    } else if contribution.settled {
+        contribution.accepted
+    } else {
+        contribution.amount
+    }
+}
+
+// Number of lottery tickets a user receives for their tier. A tier above the
+// table (after levels were removed) gets the top level's tickets.
+fn tickets_for_tier(tier_config: &TierConfig, tier: u8) -> u64 {
+    let index = usize::from(tier).min(tier_config.tiers.len() - 1);
+    tier_config.tiers[index].lottery_tickets
+}
+
+// Position of a ticket in a seeded random permutation of [0, total_tickets).
+// A keccak Feistel network permutes the smallest even-bit domain covering every
+// ticket, and cycle-walking maps results outside the range back into it.
+fn shuffle_ticket(seed: &[u8; 32], ticket_number: u64, total_tickets: u64) -> u64 {
+    let mut bits = 64 - total_tickets.saturating_sub(1).leading_zeros();
+    bits = (bits + bits % 2).max(2);
+    let half = bits / 2;
+    let mask = (1u64 << half) - 1;
+
+    let mut position = ticket_number;
+    loop {
+        let mut left = position >> half;
+        let mut right = position & mask;
+        for round in 0..LOTTERY_SHUFFLE_ROUNDS {
+            let hash = keccak::hashv(&[seed, &[round], &right.to_le_bytes()]).0;
+            let mut value = [0u8; 8];
+            value.copy_from_slice(&hash[..8]);
+            let mixed = left ^ (u64::from_le_bytes(value) & mask);
+            left = right;
+            right = mixed;
+        }
+        position = (left << half) | right;
+        if position < total_tickets {
+            return position;
+        }
+    }
+}
+
+// Whether a ticket wins the drawn lottery. Tickets are shuffled with the seed and
+// the first `winning_tickets` positions win, so exactly that many tickets win (every
+// ticket when the lottery is not oversubscribed).
+fn is_winning_ticket(lottery: &Lottery, ticket_number: u64) -> bool {
+    shuffle_ticket(&lottery.seed, ticket_number, lottery.total_tickets) < lottery.winning_tickets
+}
+
+// Count winning tickets in a user's contiguous ticket range
+fn count_winning_tickets(lottery: &Lottery, first_ticket: u64, ticket_count: u64) -> u64 {
+    (first_ticket..first_ticket.saturating_add(ticket_count))
+        .filter(|ticket_number| is_winning_ticket(lottery, *ticket_number))
+        .count() as u64
+}
+
+// Look up the hash of `slot` in the SlotHashes sysvar without deserializing all of it
+fn find_slot_hash(slot_hashes: &AccountInfo, slot: u64) -> Result<[u8; 32]> {
+    let data = slot_hashes.try_borrow_data()?;
+    let len = u64::from_le_bytes(
+        data[..8]
+            .try_into()
+            .map_err(|_| error!(OtonomError::SlotHashUnavailable))?,
+    ) as usize;
+
+    for entry in data[8..].chunks_exact(40).take(len) {
+        let entry_slot = u64::from_le_bytes(
+            entry[..8]
+                .try_into()
+                .map_err(|_| error!(OtonomError::SlotHashUnavailable))?,
+        );
+        if entry_slot == slot {
+            let mut hash = [0u8; 32];
+            hash.copy_from_slice(&entry[8..40]);
+            return Ok(hash);
+        }
+    }
+    err!(OtonomError::SlotHashUnavailable)
+}
+
+// Whether the project's sale window has closed. An end time of 0 means the sale
+// was never configured, and such projects accept no deposits.
+fn sale_ended(project: &Project, now: i64) -> bool {
+    project.end_time != 0 && now >= project.end_time
+}
+
+// Whether the project's raise has opened: its start time has passed or, for
+// projects without a start time, it has received its first deposit
+fn raise_started(project: &Project, now: i64) -> bool {
+    if project.start_time == 0 {
+        project.has_deposits
+    } else {
+        now >= project.start_time
+    }
+}
+
+// Pro-rata share of a deposit accepted against the hard cap, rounded up so the
+// accepted amounts always cover the hard cap and refunds never exceed the excess
+fn overflow_accepted_amount(deposited: u64, hard_cap: u64, total_raised: u64) -> Result<u64> {
+    if total_raised <= hard_cap {
+        return Ok(deposited);
+    }
+    let numerator = (deposited as u128)
+        .checked_mul(hard_cap as u128)
+        .ok_or(error!(OtonomError::ArithmeticOverflow))?;
+    let accepted = numerator
+        .checked_add(total_raised as u128 - 1)
+        .ok_or(error!(OtonomError::ArithmeticOverflow))?
+        / total_raised as u128;
+    u64::try_from(accepted).map_err(|_| error!(OtonomError::ArithmeticOverflow))
+}
+
+// Remove a refunded or withdrawn amount from the user's per-currency and OFUND
+// totals, lowering the tier if the OFUND total drops below its threshold
+fn reduce_invested_totals(
+    user_profile: &mut UserProfile,
+    ofund_mint: Pubkey,
+    payment_mint: Pubkey,
+    amount: u64,
+    tier_config: &TierConfig,
+    now: i64,
+) {
+    if let Some(total) = user_profile
+        .currency_totals
+        .iter_mut()
+        .find(|total| total.mint == payment_mint)
+    {
+        total.amount = total.amount.saturating_sub(amount);
+    }
+    if payment_mint == ofund_mint {
+        user_profile.total_invested = user_profile.total_invested.saturating_sub(amount);
+        update_tier(user_profile, tier_config, now);
+    }
+}
+
+// Transfer tokens out of a project's escrow vault, signed by the project PDA
+fn transfer_from_escrow<'info>(
+    project: &Account<'info, Project>,
+    project_vault: &InterfaceAccount<'info, TokenAccount>,
+    payment_mint: &InterfaceAccount<'info, Mint>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    let id = project.id.to_le_bytes();
+    let seeds = &[b"project".as_ref(), project.mint.as_ref(), &id, &[project.bump]];
+    let signer = &[&seeds[..]];
+
+    let cpi_accounts = token_interface::TransferChecked {
+        from: project_vault.to_account_info(),
+        mint: payment_mint.to_account_info(),
+        to: to.to_account_info(),
+        authority: project.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer);
+
+    token_interface::transfer_checked(cpi_ctx, amount, payment_mint.decimals)
+}
+
+// Recompute the user's tier from their qualifying OFUND total. Every instruction
+// that changes `total_invested` goes through here so tiers move in both directions.
+fn update_tier(user_profile: &mut UserProfile, tier_config: &TierConfig, now: i64) {
+    let tier = calculate_tier(tier_config, user_profile.total_invested);
+    set_tier(user_profile, tier, now);
+}
+
+// Update the user's tier, recording the change in their tier history
+fn set_tier(user_profile: &mut UserProfile, tier: u8, now: i64) {
+    if tier == user_profile.tier && !user_profile.tier_history.is_empty() {
+        return;
+    }
+    user_profile.tier = tier;
+    if user_profile.tier_history.len() == MAX_TIER_HISTORY {
+        user_profile.tier_history.remove(0);
+    }
+    user_profile.tier_history.push(TierChange {
+        tier,
+        timestamp: now,
+    });
+}
+
+// Lowest tier the user held throughout [from, to]. Time before registration, or
+// before the oldest retained history entry, counts as tier 0.
+fn min_tier_held(user_profile: &UserProfile, from: i64, to: i64) -> u8 {
+    let history = &user_profile.tier_history;
+    let mut held = history
+        .iter()
+        .rev()
+        .find(|change| change.timestamp <= from)
+        .map_or(0, |change| change.tier);
+    for change in history
+        .iter()
+        .filter(|change| change.timestamp > from && change.timestamp <= to)
+    {
+        held = held.min(change.tier);
+    }
+    held
+}
+
+// Tier that applies to a user for a given project: the frozen snapshot tier when
+// the project uses snapshots, otherwise the live profile tier
+fn effective_tier(
+    project: &Project,
+    user_profile: &UserProfile,
+    tier_snapshot: Option<&TierSnapshot>,
+) -> Result<u8> {
+    if project.tier_snapshot_time == 0 {
+        return Ok(user_profile.tier);
+    }
+    let snapshot = tier_snapshot.ok_or(error!(OtonomError::TierSnapshotRequired))?;
+    Ok(snapshot.tier)
+}
+
+// Whether `signer` holds `role`. The mint admin implicitly holds every role.
+fn has_role(mint_authority: &MintAuthority, roles: Option<&Roles>, signer: Pubkey, role: u8) -> bool {
+    if signer == mint_authority.admin {
+        return true;
+    }
+    roles.map_or(false, |roles| {
+        roles
+            .members
+            .iter()
+            .any(|grant| grant.member == signer && grant.roles & role == role)
+    })
+}
+
+// Grant one or more roles (bitmask) to a member
+fn add_role(roles: &mut Roles, member: Pubkey, role: u8) -> Result<()> {
+    match roles.members.iter_mut().find(|grant| grant.member == member) {
+        Some(grant) => grant.roles |= role,
+        None => {
+            require!(
+                roles.members.len() < MAX_ROLE_MEMBERS,
+                OtonomError::MaxRoleMembersReached
+            );
+            roles.members.push(RoleGrant {
+                member,
+                roles: role,
+            });
+        }
+    }
+
+    msg!("Granted roles {:#04x} to {}", role, member);
+    Ok(())
+}
+
+// Revoke one or more roles (bitmask) from a member
+fn remove_role(roles: &mut Roles, member: Pubkey, role: u8) -> Result<()> {
+    let grant = roles
+        .members
+        .iter_mut()
+        .find(|grant| grant.member == member)
+        .ok_or(error!(OtonomError::RoleNotFound))?;
+    grant.roles &= !role;
+    roles.members.retain(|grant| grant.roles != 0);
+
+    msg!("Revoked roles {:#04x} from {}", role, member);
+    Ok(())
+}
+
+// Whether `signer` may propose, approve or cancel admin actions: the admin itself,
+// or a member of the multisig that currently holds the admin role
+fn is_admin_approver(
+    mint_authority: &MintAuthority,
+    admin_multisig: Option<&Account<AdminMultisig>>,
+    signer: Pubkey,
+) -> bool {
+    if signer == mint_authority.admin {
+        return true;
+    }
+    admin_multisig.map_or(false, |multisig| {
+        multisig.key() == mint_authority.admin && multisig.signers.contains(&signer)
+    })
+}
+
+// Whether the admin approved a direct admin instruction: the admin signed it, or,
+// when the admin is the program multisig, at least `threshold` members signed the
+// transaction (as `signer` or among `remaining_accounts`)
+fn admin_signed(
+    mint_authority: &MintAuthority,
+    admin_multisig: Option<&Account<AdminMultisig>>,
+    signer: Pubkey,
+    remaining_accounts: &[AccountInfo],
+) -> bool {
+    if signer == mint_authority.admin {
+        return true;
+    }
+    admin_multisig.map_or(false, |multisig| {
+        if multisig.key() != mint_authority.admin {
+            return false;
+        }
+        let mut approvers: Vec<Pubkey> = Vec::new();
+        let co_signers = remaining_accounts
+            .iter()
+            .filter(|info| info.is_signer)
+            .map(|info| info.key());
+        for member in std::iter::once(signer).chain(co_signers) {
+            if multisig.signers.contains(&member) && !approvers.contains(&member) {
+                approvers.push(member);
+            }
+        }
+        approvers.len() >= usize::from(multisig.threshold)
+    })
+}
+
+// Add newly minted OFUND grants to the global statistics
+fn add_granted(stats: &mut GlobalStats, amount: u64) -> Result<()> {
+    stats.total_granted = stats
+        .total_granted
+        .checked_add(amount)
+        .ok_or(error!(OtonomError::ArithmeticOverflow))?;
+    Ok(())
+}
+
+// Case-insensitive hash of a project name, used as the name reservation seed
+fn project_name_hash(name: &str) -> [u8; 32] {
+    keccak::hash(name.to_lowercase().as_bytes()).0
+}
+
+// Owner or co-owner of a project
+fn is_project_owner(project: &Project, signer: Pubkey) -> bool {
+    project.authority == signer || project.co_owners.contains(&signer)
+}
+
+// Benefits for the user's tier in a project; 1x with no delay when unconfigured
+fn tier_benefit(
+    project: &Project,
+    user_profile: &UserProfile,
+    tier_snapshot: Option<&TierSnapshot>,
+) -> Result<TierBenefit> {
+    if project.tier_benefits.is_empty() {
+        return Ok(TierBenefit {
+            weight_bps: BPS_DENOMINATOR as u16,
+            access_delay: 0,
+        });
+    }
+    let tier = effective_tier(project, user_profile, tier_snapshot)?;
+    let index = usize::from(tier).min(project.tier_benefits.len() - 1);
+    Ok(project.tier_benefits[index].clone())
+}
+
+// Validate a tier level's fields, independent of its position in the table
+fn validate_tier_level(level: &TierLevel) -> Result<()> {
+    require!(
+        !level.name.is_empty() && level.name.len() <= MAX_TIER_NAME_LEN,
+        OtonomError::InvalidTierLevel
+    );
+    require!(
+        level.lottery_tickets > 0 && level.lottery_tickets <= MAX_TICKETS_PER_USER,
+        OtonomError::InvalidTierLevel
+    );
+    Ok(())
+}
+
+// Loyalty points earned for `amount` base units of `payment_mint` with `decimals`.
+// Mints without a configured rate earn no points.
+fn loyalty_points_for(
+    points_config: &PointsConfig,
+    payment_mint: Pubkey,
+    amount: u64,
+    decimals: u8,
+) -> u64 {
+    let rate = match points_config.rates.iter().find(|rate| rate.mint == payment_mint) {
+        Some(rate) => rate.points_per_token,
+        None => return 0,
+    };
+    let scale = match 10u128.checked_pow(u32::from(decimals)) {
+        Some(scale) => scale,
+        None => return 0,
+    };
+    let points = u128::from(amount) * u128::from(rate) / scale;
+    u64::try_from(points).unwrap_or(u64::MAX)
+}
+
+// Take back the pending points a contribution earned on `amount` of its `deposited`
+// total when that amount leaves escrow. The share is rounded up and taken from the
+// points recorded on the contribution, so splitting exits or rate changes cannot keep points.
+fn forfeit_loyalty_points(contribution: &mut Contribution, amount: u64, deposited: u64) {
+    let points = if deposited == 0 || amount >= deposited {
+        contribution.points_earned
+    } else {
+        let share = (u128::from(contribution.points_earned) * u128::from(amount))
+            .div_ceil(u128::from(deposited));
+        u64::try_from(share).unwrap_or(u64::MAX)
+    };
+    let points = points.min(contribution.points_earned);
+    contribution.points_earned -= points;
+}
+
+// Extra lottery tickets bought with claimed loyalty points; 0 when disabled
+fn bonus_lottery_tickets(points_config: &PointsConfig, user_profile: &UserProfile) -> u64 {
+    if points_config.points_per_lottery_ticket == 0 {
+        return 0;
+    }
+    (user_profile.loyalty_points / points_config.points_per_lottery_ticket)
+        .min(points_config.max_bonus_tickets)
+}
+
+// Close a program-owned account that is not loaded as an Anchor `Account`,
+// sending its rent to `destination`
+fn close_program_account(info: &AccountInfo, destination: &AccountInfo) -> Result<()> {
+    let lamports = destination
+        .lamports()
+        .checked_add(info.lamports())
+        .ok_or(error!(OtonomError::ArithmeticOverflow))?;
+    **destination.try_borrow_mut_lamports()? = lamports;
+    **info.try_borrow_mut_lamports()? = 0;
+    info.assign(&system_program::ID);
+    info.realloc(0, false)?;
+    Ok(())
+}
+
+// Check a points config's per-mint rates: bounded, with each mint listed once
+fn validate_points_rates(rates: &[PointsRate]) -> Result<()> {
+    require!(rates.len() <= MAX_POINTS_RATES, OtonomError::InvalidPointsConfig);
+    for (i, rate) in rates.iter().enumerate() {
+        require!(
+            !rates[..i].iter().any(|other| other.mint == rate.mint),
+            OtonomError::InvalidPointsConfig
+        );
+    }
+    Ok(())
+}
+
+// Append a tier level above the current top level. Levels are never inserted
+// below existing ones, so stored tiers keep pointing at the same level.
+fn push_tier_level(tier_config: &mut TierConfig, level: TierLevel) -> Result<()> {
+    validate_tier_level(&level)?;
+    let tiers = &mut tier_config.tiers;
+    require!(tiers.len() < MAX_TIERS, OtonomError::TooManyTiers);
+    require!(
+        tiers.last().map_or(true, |top| top.threshold < level.threshold),
+        OtonomError::InvalidTierLevel
+    );
+
+    msg!("Tier '{}' added at level {}", level.name, tiers.len());
+    tiers.push(level);
+    Ok(())
+}
+
+// Replace a tier level; its threshold must stay between its neighbours'
+fn replace_tier_level(tier_config: &mut TierConfig, index: u8, level: TierLevel) -> Result<()> {
+    validate_tier_level(&level)?;
+    let tiers = &mut tier_config.tiers;
+    let index = usize::from(index);
+    require!(index < tiers.len(), OtonomError::TierLevelNotFound);
+    if index == 0 {
+        require!(level.threshold == 0, OtonomError::InvalidTierLevel);
+    } else {
+        require!(
+            tiers[index - 1].threshold < level.threshold,
+            OtonomError::InvalidTierLevel
+        );
+    }
+    if let Some(next) = tiers.get(index + 1) {
+        require!(level.threshold < next.threshold, OtonomError::InvalidTierLevel);
+    }
+
+    msg!("Tier level {} updated to '{}'", index, level.name);
+    tiers[index] = level;
+    Ok(())
+}
+
+// Remove the top tier level. Only the top level can be removed, so no other
+// level is renumbered, and the base level (threshold 0) always remains.
+// Users at the removed level keep it until their next update or refresh_tier.
+fn pop_tier_level(tier_config: &mut TierConfig) -> Result<()> {
+    let tiers = &mut tier_config.tiers;
+    require!(tiers.len() > 1, OtonomError::InvalidTierLevel);
+
+    let removed = tiers.pop().ok_or(error!(OtonomError::TierLevelNotFound))?;
+    msg!("Tier '{}' removed", removed.name);
+    Ok(())
+}
+
+// Constants for distributor size (bitmap must keep the account under the 10KB CPI limit)
+const MAX_DISTRIBUTOR_NODES: u64 = 80_000;
+const DISTRIBUTOR_BASE_SPACE: usize =
+    8  + // discriminator
+    1  + // bump
+    32 + // mint
+    32 + // creator
+    8  + // distributor_id
+    32 + // merkle_root
+    8  + // max_total_claim
+    8  + // max_num_nodes
+    8  + // total_amount_claimed
+    8  + // num_nodes_claimed
+    4;   // bitmap length prefix
+
+// Lottery draw timing: the committed slot is a few slots ahead, and its hash stays
+// in the SlotHashes sysvar for the most recent 512 slots
+const LOTTERY_COMMIT_DELAY_SLOTS: u64 = 10;
+const LOTTERY_SHUFFLE_ROUNDS: u8 = 4;
+const MAX_SLOT_HASH_AGE: u64 = 512;
+
+// Most lottery tickets one user can hold, bounding the work to count their wins
+const MAX_TICKETS_PER_USER: u64 = 64;
+
+// Bonus OFUND minted to a referrer for each referred registration (5,000)
+const REFERRAL_BONUS: u64 = 5_000 * 10u64.pow(9); // 9 decimals
+
+// Timelock bounds for queued admin actions
+const MIN_TIMELOCK_DELAY: i64 = 2 * 24 * 60 * 60; // 48 hours
+const TIMELOCK_GRACE_PERIOD: i64 = 14 * 24 * 60 * 60; // executable for 14 days after eta
+
+// Constants for pending action size
+const PENDING_ACTION_SPACE: usize =
+    8  + // discriminator
+    1  + // bump
+    32 + // mint
+    8  + // action_id
+    32 + // proposer
+    1 + 4 + MAX_POINTS_RATES * POINTS_RATE_SIZE + 8 + 8 + // action (largest variant: UpdatePointsConfig)
+    8  + // queued_at
+    8  + // eta
+    4  + // approvals length prefix
+    MAX_MULTISIG_SIGNERS * 32;
+
+// Constants for KYC account sizes
+const KYC_CONFIG_SPACE: usize = 8 + 1 + 32 + 32; // discriminator + bump + mint + attester
+const ATTESTATION_SPACE: usize =
+    8  + // discriminator
+    1  + // bump
+    32 + // mint
+    32 + // user
+    32 + // attester
+    8  + // issued_at
+    8  + // expires_at
+    1;   // revoked
+
+// Constants for registration limiter size
+const REGISTRATION_LIMITER_SPACE: usize =
+    8  + // discriminator
+    1  + // bump
+    32 + // mint
+    8  + // window_duration
+    4  + // max_registrations_per_window (0 = unlimited)
+    8  + // registration_fee (lamports)
+    32 + // treasury
+    8  + // window_start
+    4;   // registrations_in_window
+
+// Constants for global stats size
+const GLOBAL_STATS_SPACE: usize =
+    8  + // discriminator
+    1  + // bump
+    32 + // mint
+    8  + // user_count
+    8  + // project_count
+    8  + // total_ofund_raised
+    8  + // total_granted
+    8  + // total_fees (lamports)
+    8;   // total_ofund_penalties (OFUND exit penalties)
+
+// Constants for blocklist entry size
+const REGISTRATION_SPACE: usize = 8 + 1 + 32 + 8; // discriminator + bump + user + registered_at
+const BLOCKLIST_ENTRY_SPACE: usize = 8 + 1 + 32 + 32 + 8; // discriminator + bump + mint + wallet + added_at
+
+// Constants for admin multisig size
+const MAX_MULTISIG_SIGNERS: usize = 10;
+const ADMIN_MULTISIG_SPACE: usize =
+    8  + // discriminator
+    1  + // bump
+    32 + // mint
+    32 + // create_key
+    4  + // signers length prefix
+    MAX_MULTISIG_SIGNERS * 32 +
+    1;   // threshold
+
+// Role flags granted through the roles account
+const ROLE_PROJECT_CURATOR: u8 = 1 << 0;
+const ROLE_PAUSER: u8 = 1 << 1;
+const ROLE_FEE_MANAGER: u8 = 1 << 2;
+const ROLE_METADATA_MANAGER: u8 = 1 << 3;
+const ROLE_GRANT_OPERATOR: u8 = 1 << 4;
+const ALL_ROLES: u8 = ROLE_PROJECT_CURATOR
+    | ROLE_PAUSER
+    | ROLE_FEE_MANAGER
+    | ROLE_METADATA_MANAGER
+    | ROLE_GRANT_OPERATOR;
+
+// Constants for roles account size
+const MAX_ROLE_MEMBERS: usize = 16;
+const ROLES_SPACE: usize =
+    8  + // discriminator
+    1  + // bump
+    32 + // mint
+    4  + // members length prefix
+    MAX_ROLE_MEMBERS * (32 + 1); // member (32) + roles (u8)
+
+// Constants for user profile size and investment cap
+const MAX_INVESTS: usize = 20; // maximum number of investments stored per user
+const INVEST_SIZE: usize = 32 + 32 + 8 + 8; // project (32) + mint (32) + amount (u64) + timestamp (i64)
+const MAX_CURRENCIES: usize = 5; // maximum number of payment mints tracked per user
+const CURRENCY_TOTAL_SIZE: usize = 32 + 8; // mint (32) + amount (u64)
+const MAX_TIER_HISTORY: usize = 8; // most recent tier changes kept per user
+const TIER_CHANGE_SIZE: usize = 1 + 8; // tier (u8) + timestamp (i64)
+const USER_PROFILE_SPACE: usize =
+    8  + // discriminator
+    32 + // user pubkey
+    1  + // bump
+    1  + // tier
+    8  + // total_invested (OFUND only)
+    4  + // vec length prefix (Anchor serialises Vec with u32 length)
+    MAX_INVESTS * INVEST_SIZE +
+    4  + // currency_totals length prefix
+    MAX_CURRENCIES * CURRENCY_TOTAL_SIZE +
+    1 + 32 + // referrer (Option<Pubkey>)
+    4  + // referral_count
+    8  + // referral_rewards
+    4  + // tier_history length prefix
+    MAX_TIER_HISTORY * TIER_CHANGE_SIZE +
+    8;   // loyalty_points
+
+// Constants for project size
+const MAX_PROJECT_NAME_LEN: usize = 50;
+const MAX_CO_OWNERS: usize = 5;
+const PROJECT_SPACE: usize =
+    8  + // discriminator
+    8  + // id
+    4 + MAX_PROJECT_NAME_LEN + // name
+    1  + // bump
+    32 + // authority
+    32 + // vault
+    8  + // total_raised
+    32 + // mint (OFUND)
+    32 + // payment_mint
+    1  + // status
+    1  + // has_lottery
+    1  + // sale_mode
+    8  + // hard_cap
+    8  + // start_time
+    8  + // end_time
+    8  + // proceeds_withdrawn
+    4  + // unsettled_contributions
+    8  + // tier_snapshot_time
+    8  + // min_tier_hold
+    1  + // requires_kyc
+    PROJECT_METADATA_SIZE +
+    1 + 32 + // pending_authority
+    4 + MAX_CO_OWNERS * 32 + // co_owners
+    8  + // min_investment
+    8  + // max_investment
+    2  + // exit_penalty_bps
+    32 + // exit_penalty_treasury
+    4 + MAX_TIER_BENEFITS * TIER_BENEFIT_SIZE + // tier_benefits
+    4  + // open_contributions
+    1  + // has_deposits
+    8  + // max_allocation
+    1;   // paused
+
+// Basis point denominator for percentage settings
+const BPS_DENOMINATOR: u64 = 10_000;
+
+// Constants for per-project tier benefits, at most one entry per tier level
+const MAX_TIER_BENEFITS: usize = MAX_TIERS;
+const TIER_BENEFIT_SIZE: usize = 2 + 8; // weight_bps (u16) + access_delay (i64)
+
+// Constants for program config size
+const PROGRAM_CONFIG_SPACE: usize =
+    8  + // discriminator
+    1  + // bump
+    32;  // ofund_mint
+
+// Constants for tier table size
+const MAX_TIERS: usize = 16;
+const MAX_TIER_NAME_LEN: usize = 32;
+const TIER_LEVEL_SIZE: usize = 8 + 4 + MAX_TIER_NAME_LEN + 8; // threshold + name + lottery_tickets
+const TIER_CONFIG_SPACE: usize =
+    8  + // discriminator
+    1  + // bump
+    32 + // mint
+    4 + MAX_TIERS * TIER_LEVEL_SIZE; // tiers
+
+// Constants for loyalty points config size
+const MAX_POINTS_RATES: usize = 8;
+const POINTS_RATE_SIZE: usize = 32 + 8; // mint (32) + points_per_token (u64)
+const POINTS_CONFIG_SPACE: usize =
+    8  + // discriminator
+    1  + // bump
+    32 + // mint
+    4 + MAX_POINTS_RATES * POINTS_RATE_SIZE + // rates
+    8  + // points_per_lottery_ticket
+    8;   // max_bonus_tickets
+
+// Constants for project name reservation size
+const PROJECT_NAME_SPACE: usize = 8 + 1 + 32; // discriminator + bump + project
+
+// Constants for project metadata limits
+const MAX_METADATA_URI_LEN: usize = 200;
+const MAX_WEBSITE_LEN: usize = 100;
+const MAX_SOCIAL_LEN: usize = 64;
+const PROJECT_METADATA_SIZE: usize =
+    4 + MAX_METADATA_URI_LEN + // uri
+    32 + // content_hash
+    1  + // category
+    4 + MAX_WEBSITE_LEN + // website
+    3 * (4 + MAX_SOCIAL_LEN); // twitter, telegram, discord
+
+// Project lifecycle
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectStatus {
+    Active,
+    Settled,
+    Cancelled,
+}
+
+// Constants for lottery account sizes
+const LOTTERY_SPACE: usize =
+    8  + // discriminator
+    1  + // bump
+    32 + // project
+    8  + // registration_end
+    8  + // investment_end
+    8  + // winning_tickets
+    8  + // allocation_per_ticket
+    8  + // total_tickets
+    8  + // commit_slot
+    32 + // seed
+    1;   // drawn
+const LOTTERY_TICKET_SPACE: usize =
+    8  + // discriminator
+    1  + // bump
+    32 + // project
+    32 + // user
+    8  + // first_ticket
+    8  + // ticket_count
+    8  + // invested
+    1 + 8; // wins
+
+// Project category shown by the frontend
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProjectCategory {
+    #[default]
+    Uncategorized,
+    DeFi,
+    Gaming,
+    Infrastructure,
+    Ai,
+    Nft,
+    Social,
+    Other,
+}
+
+// One level of the tier table
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TierLevel {
+    pub threshold: u64,
+    pub name: String,
+    pub lottery_tickets: u64,
+}
+
+// Perks for one tier in a project. `weight_bps` scales the project's maximum
+// per-investor allocation (10000 = 1x) and `access_delay` is seconds after `start_time` the tier may invest.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TierBenefit {
+    pub weight_bps: u16,
+    pub access_delay: i64,
+}
+
+// Descriptive project metadata; full details live off-chain at `uri`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct ProjectMetadata {
+    pub uri: String,
+    pub content_hash: [u8; 32], // hash of the document at `uri`
+    pub category: ProjectCategory,
+    pub website: String,
+    pub twitter: String,
+    pub telegram: String,
+    pub discord: String,
+}
+
+// How a project handles deposits above its hard cap
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum SaleMode {
+    Standard, // reject investments above the hard cap
+    Overflow, // accept all deposits, refund pro-rata excess after close
+}
+
+// Constants for contribution size
+const CONTRIBUTION_SPACE: usize =
+    8  + // discriminator
+    1  + // bump
+    32 + // project
+    32 + // user
+    8  + // amount
+    8  + // accepted
+    1  + // settled
+    1  + // refunded
+    8;   // points_earned
+
+// Investment struct
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Investment {
+    pub project: Pubkey,
+    pub mint: Pubkey, // payment mint the amount is denominated in
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+// Tier change entry in a user's tier history
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TierChange {
+    pub tier: u8,
+    pub timestamp: i64,
+}
+
+// Constants for tier snapshot size
+const TIER_SNAPSHOT_SPACE: usize =
+    8  + // discriminator
+    1  + // bump
+    32 + // project
+    32 + // user
+    1  + // tier
+    8;   // snapshot_time
+
+// Admin changes that must go through the timelock
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum AdminAction {
+    TransferAdmin { new_admin: Pubkey },
+    GrantRole { member: Pubkey, role: u8 },
+    RevokeRole { member: Pubkey, role: u8 },
+    SetAttester { attester: Pubkey },
+    UpdateRegistrationLimits {
+        window_duration: i64,
+        max_registrations_per_window: u32,
+        registration_fee: u64,
+        treasury: Pubkey,
+    },
+    AddTierLevel { level: TierLevel },
+    UpdateTierLevel { index: u8, level: TierLevel },
+    RemoveTierLevel,
+    UpdatePointsConfig {
+        rates: Vec<PointsRate>,
+        points_per_lottery_ticket: u64,
+        max_bonus_tickets: u64,
+    },
+}
+
+// Roles granted to a single member
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RoleGrant {
+    pub member: Pubkey,
+    pub roles: u8,
+}
+
+// Running total invested per payment mint
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CurrencyTotal {
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+// Initialize Mint Authority for New Token
+#[derive(Accounts)]
+pub struct InitializeMint<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        mint::decimals = 9,
+        mint::authority = mint_authority_pda,
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"mint-authority", mint.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: PDA used as mint authority
+    pub mint_authority_pda: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 1 + 32 + 32 + 4 + 32 + 4 + 16 + 4 + 128 + 1,
+        seeds = [b"authority", mint.key().as_ref()],
+        bump,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+// Initialize Mint Authority for Existing Mint
+#[derive(Accounts)]
+pub struct InitializeExistingMint<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    // Note: Not initializing the mint, just referencing it
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"mint-authority", mint.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: PDA used as mint authority
+    pub mint_authority_pda: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 1 + 32 + 32 + 4 + 32 + 4 + 16 + 4 + 128 + 1,
+        seeds = [b"authority", mint.key().as_ref()],
+        bump,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+// Register User
+#[derive(Accounts)]
+pub struct RegisterUser<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = USER_PROFILE_SPACE,
+        seeds = [b"user-profile", user.key().as_ref()],
+        bump,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    // Permanent record that this wallet has registered; never closed
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = REGISTRATION_SPACE,
+        seeds = [b"registration", mint.key().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub registration: Account<'info, Registration>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key(),
+        constraint = user_token_account.mint == mint.key(),
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"authority", mint.key().as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    /// CHECK: PDA that is the mint authority
+    #[account(
+        seeds = [b"mint-authority", mint.key().as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority_pda: UncheckedAccount<'info>,
+
+    /// CHECK: blocklist PDA for the user; registration fails if it exists
+    #[account(
+        seeds = [b"blocklist", mint.key().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub blocklist_entry: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"registration-limiter", mint.key().as_ref()],
+        bump = registration_limiter.bump,
+    )]
+    pub registration_limiter: Account<'info, RegistrationLimiter>,
+
+    /// CHECK: receives the registration fee; checked against registration_limiter.treasury
+    #[account(mut, address = registration_limiter.treasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global-stats", mint.key().as_ref()],
+        bump = global_stats.bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    // Optional referrer, credited with a bonus on registration
+    #[account(mut)]
+    pub referrer_profile: Option<Account<'info, UserProfile>>,
+
+    #[account(mut)]
+    pub referrer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [b"program-config"],
+        bump = program_config.bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        seeds = [b"tier-config", program_config.ofund_mint.as_ref()],
+        bump = tier_config.bump,
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+// Initialize a new project
+#[derive(Accounts)]
+pub struct InitializeProject<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // OFUND mint that drives tiers
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    // Counter source for project IDs
+    #[account(
+        mut,
+        seeds = [b"global-stats", mint.key().as_ref()],
+        bump = global_stats.bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = PROJECT_SPACE,
+        seeds = [b"project", mint.key().as_ref(), &global_stats.project_count.to_le_bytes()],
+        bump,
+    )]
+    pub project: Account<'info, Project>,
+
+    // Escrow vault owned by the project PDA, so deposits can always be refunded
+    #[account(
+        mut,
+        constraint = project_vault.owner == project.key() @ OtonomError::EscrowVaultRequired,
+        constraint = project_vault.mint == payment_mint.key(),
+    )]
+    pub project_vault: InterfaceAccount<'info, TokenAccount>,
+
+    // Mint the project accepts investments in (e.g. USDC or OFUND)
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+// Invest in a project
+#[derive(Accounts)]
+pub struct InvestInProject<'info> {
+    #[account(mut)]
+    pub investor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user-profile", investor.key().as_ref()],
+        bump = user_profile.bump,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"project", project.mint.as_ref(), &project.id.to_le_bytes()],
+        bump = project.bump,
+    )]
+    pub project: Account<'info, Project>,
+
+    #[account(
+        mut,
+        constraint = investor_token_account.owner == investor.key(),
+        constraint = investor_token_account.mint == payment_mint.key(),
+    )]
+    pub investor_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = project.vault,
+        constraint = project_vault.mint == payment_mint.key(),
+    )]
+    pub project_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = project.payment_mint)]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: blocklist PDA for the investor; investing fails if it exists
+    #[account(
+        seeds = [b"blocklist", project.mint.as_ref(), investor.key().as_ref()],
+        bump,
+    )]
+    pub blocklist_entry: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global-stats", project.mint.as_ref()],
+        bump = global_stats.bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    #[account(
+        init_if_needed,
+        payer = investor,
+        space = CONTRIBUTION_SPACE,
+        seeds = [b"contribution", project.key().as_ref(), investor.key().as_ref()],
+        bump,
+    )]
+    pub contribution: Account<'info, Contribution>,
+
+    // Required when the project runs a lottery
+    #[account(
+        seeds = [b"lottery", project.key().as_ref()],
+        bump = lottery.bump,
+    )]
+    pub lottery: Option<Account<'info, Lottery>>,
+
+    #[account(
+        mut,
+        seeds = [b"lottery-ticket", project.key().as_ref(), investor.key().as_ref()],
+        bump = lottery_ticket.bump,
+    )]
+    pub lottery_ticket: Option<Account<'info, LotteryTicket>>,
+
+    // Required when the project requires KYC
+    #[account(
+        seeds = [b"attestation", project.mint.as_ref(), investor.key().as_ref()],
+        bump = attestation.bump,
+    )]
+    pub attestation: Option<Account<'info, Attestation>>,
+
+    // Required when the project requires KYC
+    #[account(
+        seeds = [b"kyc-config", project.mint.as_ref()],
+        bump = kyc_config.bump,
+    )]
+    pub kyc_config: Option<Account<'info, KycConfig>>,
+
+    // Required when the project has tier benefits and uses tier snapshots
+    #[account(
+        seeds = [b"tier-snapshot", project.key().as_ref(), investor.key().as_ref()],
+        bump = tier_snapshot.bump,
+    )]
+    pub tier_snapshot: Option<Account<'info, TierSnapshot>>,
+
+    #[account(
+        seeds = [b"program-config"],
+        bump = program_config.bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        seeds = [b"tier-config", program_config.ofund_mint.as_ref()],
+        bump = tier_config.bump,
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+
+    #[account(
+        seeds = [b"points-config", program_config.ofund_mint.as_ref()],
+        bump = points_config.bump,
+    )]
+    pub points_config: Account<'info, PointsConfig>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+// Initialize a Merkle distributor
+#[derive(Accounts)]
+#[instruction(distributor_id: u64, merkle_root: [u8; 32], max_total_claim: u64, max_num_nodes: u64)]
+pub struct InitializeDistributor<'info> {
+    // Admin or grant operator
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    #[account(
+        seeds = [b"authority", mint.key().as_ref()],
+        bump = mint_authority.bump,
+        has_one = mint,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    #[account(
+        seeds = [b"roles", mint.key().as_ref()],
+        bump = roles.bump,
+    )]
+    pub roles: Option<Account<'info, Roles>>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = operator,
+        // Sized before any constraint runs, so clamp the request to keep the rent
+        // calculation in range and let the constraint below reject it
+        space = DISTRIBUTOR_BASE_SPACE + bitmap_len(max_num_nodes.min(MAX_DISTRIBUTOR_NODES)),
+        seeds = [b"distributor", mint.key().as_ref(), &distributor_id.to_le_bytes()],
+        bump,
+        constraint = max_num_nodes > 0 && max_num_nodes <= MAX_DISTRIBUTOR_NODES
+            @ OtonomError::InvalidDistributorSize,
+    )]
+    pub distributor: Account<'info, Distributor>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Claim from a Merkle distributor
+#[derive(Accounts)]
+pub struct ClaimAirdrop<'info> {
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"distributor", mint.key().as_ref(), &distributor.distributor_id.to_le_bytes()],
+        bump = distributor.bump,
+        has_one = mint,
+    )]
+    pub distributor: Account<'info, Distributor>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = claimant_token_account.owner == claimant.key(),
+        constraint = claimant_token_account.mint == mint.key(),
+    )]
+    pub claimant_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"global-stats", mint.key().as_ref()],
+        bump = global_stats.bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    /// CHECK: PDA that is the mint authority
+    #[account(
+        seeds = [b"mint-authority", mint.key().as_ref()],
+        bump,
+    )]
+    pub mint_authority_pda: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Settle a project
+#[derive(Accounts)]
+pub struct SettleProject<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"project", project.mint.as_ref(), &project.id.to_le_bytes()],
+        bump = project.bump,
+        has_one = authority @ OtonomError::Unauthorized,
+    )]
+    pub project: Account<'info, Project>,
+}
+
+// Close a settled project
+#[derive(Accounts)]
+pub struct CloseProject<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"project", project.mint.as_ref(), &project.id.to_le_bytes()],
+        bump = project.bump,
+        has_one = authority @ OtonomError::Unauthorized,
+        close = authority,
+    )]
+    pub project: Account<'info, Project>,
+
+    #[account(mut, address = project.vault)]
+    pub project_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: name reservation PDA for the project's name, if any; released in the handler
+    #[account(
+        mut,
+        seeds = [b"project-name", project.mint.as_ref(), &project_name_hash(&project.name)],
+        bump,
+    )]
+    pub project_name: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Close a user profile
+#[derive(Accounts)]
+pub struct CloseUserProfile<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user-profile", user.key().as_ref()],
+        bump = user_profile.bump,
+        has_one = user @ OtonomError::Unauthorized,
+        close = user,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+}
+
+// Configure a project lottery
+#[derive(Accounts)]
+pub struct InitializeLottery<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"project", project.mint.as_ref(), &project.id.to_le_bytes()],
+        bump = project.bump,
+        has_one = authority @ OtonomError::Unauthorized,
+    )]
+    pub project: Account<'info, Project>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = LOTTERY_SPACE,
+        seeds = [b"lottery", project.key().as_ref()],
+        bump,
+    )]
+    pub lottery: Account<'info, Lottery>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Register lottery tickets
+#[derive(Accounts)]
+pub struct RegisterLotteryTickets<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [b"user-profile", user.key().as_ref()],
+        bump = user_profile.bump,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(address = lottery.project)]
+    pub project: Account<'info, Project>,
+
+    #[account(
+        mut,
+        seeds = [b"lottery", lottery.project.as_ref()],
+        bump = lottery.bump,
+    )]
+    pub lottery: Account<'info, Lottery>,
+
+    // Required when the project uses tier snapshots
+    #[account(
+        seeds = [b"tier-snapshot", lottery.project.as_ref(), user.key().as_ref()],
+        bump = tier_snapshot.bump,
+    )]
+    pub tier_snapshot: Option<Account<'info, TierSnapshot>>,
+
+    #[account(
+        init,
+        payer = user,
+        space = LOTTERY_TICKET_SPACE,
+        seeds = [b"lottery-ticket", lottery.project.as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub lottery_ticket: Account<'info, LotteryTicket>,
+
+    #[account(
+        seeds = [b"program-config"],
+        bump = program_config.bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        seeds = [b"tier-config", program_config.ofund_mint.as_ref()],
+        bump = tier_config.bump,
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+
+    #[account(
+        seeds = [b"points-config", program_config.ofund_mint.as_ref()],
+        bump = points_config.bump,
+    )]
+    pub points_config: Account<'info, PointsConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Commit the lottery draw slot
+#[derive(Accounts)]
+pub struct CommitLotteryDraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"lottery", lottery.project.as_ref()],
+        bump = lottery.bump,
+    )]
+    pub lottery: Account<'info, Lottery>,
+}
+
+// Draw the lottery
+#[derive(Accounts)]
+pub struct DrawLottery<'info> {
+    #[account(
+        mut,
+        seeds = [b"lottery", lottery.project.as_ref()],
+        bump = lottery.bump,
+    )]
+    pub lottery: Account<'info, Lottery>,
+
+    /// CHECK: SlotHashes sysvar, parsed manually to avoid deserializing every entry
+    #[account(address = anchor_lang::solana_program::sysvar::slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+}
+
+// Configure a project's sale
+#[derive(Accounts)]
+pub struct ConfigureSale<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"project", project.mint.as_ref(), &project.id.to_le_bytes()],
+        bump = project.bump,
+        has_one = authority @ OtonomError::Unauthorized,
+    )]
+    pub project: Account<'info, Project>,
+
+    #[account(address = project.vault)]
+    pub project_vault: InterfaceAccount<'info, TokenAccount>,
+}
+
+// Configure a project's ticket size limits
+#[derive(Accounts)]
+pub struct ConfigureTicketSize<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"project", project.mint.as_ref(), &project.id.to_le_bytes()],
+        bump = project.bump,
+        has_one = authority @ OtonomError::Unauthorized,
+    )]
+    pub project: Account<'info, Project>,
+}
+
+// Settle an overflow contribution
+#[derive(Accounts)]
+pub struct SettleOverflow<'info> {
+    #[account(
+        mut,
+        seeds = [b"project", project.mint.as_ref(), &project.id.to_le_bytes()],
+        bump = project.bump,
+    )]
+    pub project: Account<'info, Project>,
+
+    #[account(
+        mut,
+        seeds = [b"contribution", project.key().as_ref(), contribution.user.as_ref()],
+        bump = contribution.bump,
+    )]
+    pub contribution: Account<'info, Contribution>,
+
+    #[account(
+        mut,
+        seeds = [b"user-profile", contribution.user.as_ref()],
+        bump = user_profile.bump,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        address = project.vault,
+    )]
+    pub project_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = investor_token_account.owner == contribution.user,
+        constraint = investor_token_account.mint == payment_mint.key(),
+    )]
+    pub investor_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = project.payment_mint)]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"global-stats", project.mint.as_ref()],
+        bump = global_stats.bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    #[account(
+        seeds = [b"program-config"],
+        bump = program_config.bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        seeds = [b"tier-config", program_config.ofund_mint.as_ref()],
+        bump = tier_config.bump,
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Withdraw proceeds from a project's escrow vault
+#[derive(Accounts)]
+pub struct WithdrawProceeds<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"project", project.mint.as_ref(), &project.id.to_le_bytes()],
+        bump = project.bump,
+        has_one = authority @ OtonomError::Unauthorized,
+    )]
+    pub project: Account<'info, Project>,
+
+    #[account(
+        mut,
+        address = project.vault,
+        constraint = project_vault.owner == project.key() @ OtonomError::EscrowVaultRequired,
+    )]
+    pub project_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = authority_token_account.owner == authority.key(),
+        constraint = authority_token_account.mint == payment_mint.key(),
+    )]
+    pub authority_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = project.payment_mint)]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Configure a project's tier snapshot
+#[derive(Accounts)]
+pub struct ConfigureTierSnapshot<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"project", project.mint.as_ref(), &project.id.to_le_bytes()],
+        bump = project.bump,
+        has_one = authority @ OtonomError::Unauthorized,
+    )]
+    pub project: Account<'info, Project>,
+}
+
+// Freeze a user's tier for a project
+#[derive(Accounts)]
+pub struct SnapshotTier<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"project", project.mint.as_ref(), &project.id.to_le_bytes()],
+        bump = project.bump,
+    )]
+    pub project: Account<'info, Project>,
+
+    #[account(
+        seeds = [b"user-profile", user_profile.user.as_ref()],
+        bump = user_profile.bump,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = TIER_SNAPSHOT_SPACE,
+        seeds = [b"tier-snapshot", project.key().as_ref(), user_profile.user.as_ref()],
+        bump,
+    )]
+    pub tier_snapshot: Account<'info, TierSnapshot>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Initialize the roles account
+#[derive(Accounts)]
+pub struct InitializeRoles<'info> {
+    // Admin, or an admin multisig member co-signing with enough other members
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"authority", mint_authority.mint.as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    // Required when the admin is an on-chain multisig
+    pub admin_multisig: Option<Account<'info, AdminMultisig>>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = ROLES_SPACE,
+        seeds = [b"roles", mint_authority.mint.as_ref()],
+        bump,
+    )]
+    pub roles: Account<'info, Roles>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Grant or revoke roles
+#[derive(Accounts)]
+pub struct UpdateRoles<'info> {
+    // Admin, or an admin multisig member co-signing with enough other members
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"authority", mint_authority.mint.as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    // Required when the admin is an on-chain multisig
+    pub admin_multisig: Option<Account<'info, AdminMultisig>>,
+
+    #[account(
+        mut,
+        seeds = [b"roles", mint_authority.mint.as_ref()],
+        bump = roles.bump,
+    )]
+    pub roles: Account<'info, Roles>,
+}
+
+// Queue an admin action
+#[derive(Accounts)]
+#[instruction(action_id: u64)]
+pub struct QueueAdminAction<'info> {
+    // Admin, or a member of the admin multisig
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        seeds = [b"authority", mint_authority.mint.as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    pub admin_multisig: Option<Account<'info, AdminMultisig>>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = PENDING_ACTION_SPACE,
+        seeds = [b"pending-action", mint_authority.mint.as_ref(), &action_id.to_le_bytes()],
+        bump,
+    )]
+    pub pending_action: Account<'info, PendingAction>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Execute an admin action
+#[derive(Accounts)]
+pub struct ExecuteAdminAction<'info> {
+    #[account(
+        mut,
+        seeds = [b"authority", mint_authority.mint.as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    #[account(
+        mut,
+        seeds = [b"pending-action", mint_authority.mint.as_ref(), &pending_action.action_id.to_le_bytes()],
+        bump = pending_action.bump,
+        has_one = proposer,
+        close = proposer,
+    )]
+    pub pending_action: Account<'info, PendingAction>,
+
+    /// CHECK: receives the pending action's rent; checked against pending_action.proposer
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+
+    // Required when the admin is an on-chain multisig
+    pub admin_multisig: Option<Account<'info, AdminMultisig>>,
+
+    // Required for role grants
+    #[account(
+        mut,
+        seeds = [b"roles", mint_authority.mint.as_ref()],
+        bump = roles.bump,
+    )]
+    pub roles: Option<Account<'info, Roles>>,
+
+    // Required for attester changes
+    #[account(
+        mut,
+        seeds = [b"kyc-config", mint_authority.mint.as_ref()],
+        bump = kyc_config.bump,
+    )]
+    pub kyc_config: Option<Account<'info, KycConfig>>,
+
+    // Required for registration limit changes
+    #[account(
+        mut,
+        seeds = [b"registration-limiter", mint_authority.mint.as_ref()],
+        bump = registration_limiter.bump,
+    )]
+    pub registration_limiter: Option<Account<'info, RegistrationLimiter>>,
+
+    // Required for tier level changes
+    #[account(
+        mut,
+        seeds = [b"tier-config", mint_authority.mint.as_ref()],
+        bump = tier_config.bump,
+    )]
+    pub tier_config: Option<Account<'info, TierConfig>>,
+
+    // Required for points config changes
+    #[account(
+        mut,
+        seeds = [b"points-config", mint_authority.mint.as_ref()],
+        bump = points_config.bump,
+    )]
+    pub points_config: Option<Account<'info, PointsConfig>>,
+}
+
+// Cancel an admin action
+#[derive(Accounts)]
+pub struct CancelAdminAction<'info> {
+    // Admin, or a member of the admin multisig
+    pub canceller: Signer<'info>,
+
+    #[account(
+        seeds = [b"authority", mint_authority.mint.as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    pub admin_multisig: Option<Account<'info, AdminMultisig>>,
+
+    #[account(
+        mut,
+        seeds = [b"pending-action", mint_authority.mint.as_ref(), &pending_action.action_id.to_le_bytes()],
+        bump = pending_action.bump,
+        has_one = proposer,
+        close = proposer,
+    )]
+    pub pending_action: Account<'info, PendingAction>,
+
+    /// CHECK: receives the pending action's rent; checked against pending_action.proposer
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+}
+
+// Approve an admin action
+#[derive(Accounts)]
+pub struct ApproveAdminAction<'info> {
+    pub approver: Signer<'info>,
+
+    #[account(
+        seeds = [b"authority", mint_authority.mint.as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    #[account(address = mint_authority.admin @ OtonomError::Unauthorized)]
+    pub admin_multisig: Account<'info, AdminMultisig>,
+
+    #[account(
+        mut,
+        seeds = [b"pending-action", mint_authority.mint.as_ref(), &pending_action.action_id.to_le_bytes()],
+        bump = pending_action.bump,
+    )]
+    pub pending_action: Account<'info, PendingAction>,
+}
+
+// Create an admin multisig
+#[derive(Accounts)]
+#[instruction(create_key: Pubkey)]
+pub struct InitializeAdminMultisig<'info> {
+    // Admin, or an admin multisig member co-signing with enough other members
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"authority", mint_authority.mint.as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    // Required when the admin is already an on-chain multisig
+    pub current_admin_multisig: Option<Account<'info, AdminMultisig>>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = ADMIN_MULTISIG_SPACE,
+        seeds = [b"admin-multisig", mint_authority.mint.as_ref(), create_key.as_ref()],
+        bump,
+    )]
+    pub admin_multisig: Account<'info, AdminMultisig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Initialize the KYC config
+#[derive(Accounts)]
+pub struct InitializeKycConfig<'info> {
+    // Admin, or an admin multisig member co-signing with enough other members
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"authority", mint_authority.mint.as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    // Required when the admin is an on-chain multisig
+    pub admin_multisig: Option<Account<'info, AdminMultisig>>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = KYC_CONFIG_SPACE,
+        seeds = [b"kyc-config", mint_authority.mint.as_ref()],
+        bump,
+    )]
+    pub kyc_config: Account<'info, KycConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Issue a KYC attestation
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct IssueAttestation<'info> {
+    #[account(mut)]
+    pub attester: Signer<'info>,
+
+    #[account(
+        seeds = [b"kyc-config", kyc_config.mint.as_ref()],
+        bump = kyc_config.bump,
+        has_one = attester @ OtonomError::Unauthorized,
+    )]
+    pub kyc_config: Account<'info, KycConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = attester,
+        space = ATTESTATION_SPACE,
+        seeds = [b"attestation", kyc_config.mint.as_ref(), user.as_ref()],
+        bump,
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Revoke a KYC attestation
+#[derive(Accounts)]
+pub struct RevokeAttestation<'info> {
+    pub attester: Signer<'info>,
+
+    #[account(
+        seeds = [b"kyc-config", kyc_config.mint.as_ref()],
+        bump = kyc_config.bump,
+        has_one = attester @ OtonomError::Unauthorized,
+    )]
+    pub kyc_config: Account<'info, KycConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"attestation", kyc_config.mint.as_ref(), attestation.user.as_ref()],
+        bump = attestation.bump,
+    )]
+    pub attestation: Account<'info, Attestation>,
+}
+
+// Set a project's KYC requirement
+#[derive(Accounts)]
+pub struct SetKycRequirement<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"project", project.mint.as_ref(), &project.id.to_le_bytes()],
+        bump = project.bump,
+        has_one = authority @ OtonomError::Unauthorized,
+    )]
+    pub project: Account<'info, Project>,
+}
+
+// Add a wallet to the blocklist
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct AddToBlocklist<'info> {
+    // Admin, or an admin multisig member co-signing with enough other members
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"authority", mint_authority.mint.as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    // Required when the admin is an on-chain multisig
+    pub admin_multisig: Option<Account<'info, AdminMultisig>>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = BLOCKLIST_ENTRY_SPACE,
+        seeds = [b"blocklist", mint_authority.mint.as_ref(), wallet.as_ref()],
+        bump,
+    )]
+    pub blocklist_entry: Account<'info, BlocklistEntry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Remove a wallet from the blocklist
+#[derive(Accounts)]
+pub struct RemoveFromBlocklist<'info> {
+    // Admin, or an admin multisig member co-signing with enough other members
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"authority", mint_authority.mint.as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    // Required when the admin is an on-chain multisig
+    pub admin_multisig: Option<Account<'info, AdminMultisig>>,
+
+    #[account(
+        mut,
+        seeds = [b"blocklist", mint_authority.mint.as_ref(), blocklist_entry.wallet.as_ref()],
+        bump = blocklist_entry.bump,
+        close = admin,
+    )]
+    pub blocklist_entry: Account<'info, BlocklistEntry>,
+}
+
+// Initialize the registration limiter
+#[derive(Accounts)]
+pub struct InitializeRegistrationLimiter<'info> {
+    // Admin, or an admin multisig member co-signing with enough other members
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"authority", mint_authority.mint.as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    // Required when the admin is an on-chain multisig
+    pub admin_multisig: Option<Account<'info, AdminMultisig>>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = REGISTRATION_LIMITER_SPACE,
+        seeds = [b"registration-limiter", mint_authority.mint.as_ref()],
+        bump,
+    )]
+    pub registration_limiter: Account<'info, RegistrationLimiter>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Initialize global statistics
+#[derive(Accounts)]
+pub struct InitializeGlobalStats<'info> {
+    // Admin, or an admin multisig member co-signing with enough other members
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"authority", mint_authority.mint.as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    // Required when the admin is an on-chain multisig
+    pub admin_multisig: Option<Account<'info, AdminMultisig>>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = GLOBAL_STATS_SPACE,
+        seeds = [b"global-stats", mint_authority.mint.as_ref()],
+        bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Update project metadata
+#[derive(Accounts)]
+pub struct UpdateProjectMetadata<'info> {
+    // Project owner, co-owner, admin or metadata manager
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"project", project.mint.as_ref(), &project.id.to_le_bytes()],
+        bump = project.bump,
+    )]
+    pub project: Account<'info, Project>,
+
+    #[account(
+        seeds = [b"authority", project.mint.as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    #[account(
+        seeds = [b"roles", project.mint.as_ref()],
+        bump = roles.bump,
+    )]
+    pub roles: Option<Account<'info, Roles>>,
+}
+
+// Reserve a project name
+#[derive(Accounts)]
+pub struct ClaimProjectName<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"project", project.mint.as_ref(), &project.id.to_le_bytes()],
+        bump = project.bump,
+        has_one = authority @ OtonomError::Unauthorized,
+    )]
+    pub project: Account<'info, Project>,
+
+    // Admin or project curator approving the reservation
+    pub curator: Signer<'info>,
+
+    #[account(
+        seeds = [b"authority", project.mint.as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    #[account(
+        seeds = [b"roles", project.mint.as_ref()],
+        bump = roles.bump,
+    )]
+    pub roles: Option<Account<'info, Roles>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = PROJECT_NAME_SPACE,
+        seeds = [b"project-name", project.mint.as_ref(), &project_name_hash(&project.name)],
+        bump,
+    )]
+    pub project_name: Account<'info, ProjectName>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Owner-only project ownership changes
+#[derive(Accounts)]
+pub struct UpdateProjectOwnership<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"project", project.mint.as_ref(), &project.id.to_le_bytes()],
+        bump = project.bump,
+        has_one = authority @ OtonomError::Unauthorized,
+    )]
+    pub project: Account<'info, Project>,
+}
+
+// Accept project ownership
+#[derive(Accounts)]
+pub struct AcceptProjectAuthority<'info> {
+    pub new_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"project", project.mint.as_ref(), &project.id.to_le_bytes()],
+        bump = project.bump,
+        constraint = project.pending_authority == Some(new_authority.key()) @ OtonomError::Unauthorized,
+    )]
+    pub project: Account<'info, Project>,
+}
+
+// Cancel a project
+#[derive(Accounts)]
+pub struct CancelProject<'info> {
+    // Project owner, admin or project curator
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"project", project.mint.as_ref(), &project.id.to_le_bytes()],
+        bump = project.bump,
+    )]
+    pub project: Account<'info, Project>,
+
+    #[account(address = project.vault)]
+    pub project_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"authority", project.mint.as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    #[account(
+        seeds = [b"roles", project.mint.as_ref()],
+        bump = roles.bump,
+    )]
+    pub roles: Option<Account<'info, Roles>>,
+}
+
+// Pause or resume a project's deposits
+#[derive(Accounts)]
+pub struct SetProjectPaused<'info> {
+    // Admin or pauser
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"project", project.mint.as_ref(), &project.id.to_le_bytes()],
+        bump = project.bump,
+    )]
+    pub project: Account<'info, Project>,
+
+    #[account(
+        seeds = [b"authority", project.mint.as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    #[account(
+        seeds = [b"roles", project.mint.as_ref()],
+        bump = roles.bump,
+    )]
+    pub roles: Option<Account<'info, Roles>>,
+}
+
+// Claim a refund from a cancelled project
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    pub investor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"project", project.mint.as_ref(), &project.id.to_le_bytes()],
+        bump = project.bump,
+    )]
+    pub project: Account<'info, Project>,
+
+    #[account(
+        mut,
+        seeds = [b"contribution", project.key().as_ref(), investor.key().as_ref()],
+        bump = contribution.bump,
+    )]
+    pub contribution: Account<'info, Contribution>,
+
+    #[account(
+        mut,
+        seeds = [b"user-profile", investor.key().as_ref()],
+        bump = user_profile.bump,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        address = project.vault,
+    )]
+    pub project_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = investor_token_account.owner == investor.key(),
+        constraint = investor_token_account.mint == payment_mint.key(),
+    )]
+    pub investor_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = project.payment_mint)]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"global-stats", project.mint.as_ref()],
+        bump = global_stats.bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    #[account(
+        seeds = [b"program-config"],
+        bump = program_config.bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        seeds = [b"tier-config", program_config.ofund_mint.as_ref()],
+        bump = tier_config.bump,
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Configure a project's early-exit penalty
+#[derive(Accounts)]
+pub struct ConfigureExitPenalty<'info> {
+    // Admin or fee manager
+    pub fee_manager: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"project", project.mint.as_ref(), &project.id.to_le_bytes()],
+        bump = project.bump,
+    )]
+    pub project: Account<'info, Project>,
+
+    #[account(
+        seeds = [b"authority", project.mint.as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    #[account(
+        seeds = [b"roles", project.mint.as_ref()],
+        bump = roles.bump,
+    )]
+    pub roles: Option<Account<'info, Roles>>,
+}
+
+// Withdraw an investment before the raise closes
+#[derive(Accounts)]
+pub struct WithdrawInvestment<'info> {
+    pub investor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"project", project.mint.as_ref(), &project.id.to_le_bytes()],
+        bump = project.bump,
+    )]
+    pub project: Account<'info, Project>,
+
+    #[account(
+        mut,
+        seeds = [b"contribution", project.key().as_ref(), investor.key().as_ref()],
+        bump = contribution.bump,
+    )]
+    pub contribution: Account<'info, Contribution>,
+
+    #[account(
+        mut,
+        seeds = [b"user-profile", investor.key().as_ref()],
+        bump = user_profile.bump,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        address = project.vault,
+    )]
+    pub project_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = investor_token_account.owner == investor.key(),
+        constraint = investor_token_account.mint == payment_mint.key(),
+    )]
+    pub investor_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    // Required when the project charges an exit penalty
+    #[account(
+        mut,
+        address = project.exit_penalty_treasury,
+    )]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = project.payment_mint)]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    // Passed for lottery projects so the allocation can be reused
+    #[account(
+        mut,
+        seeds = [b"lottery-ticket", project.key().as_ref(), investor.key().as_ref()],
+        bump = lottery_ticket.bump,
+    )]
+    pub lottery_ticket: Option<Account<'info, LotteryTicket>>,
+
+    #[account(
+        mut,
+        seeds = [b"global-stats", project.mint.as_ref()],
+        bump = global_stats.bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    #[account(
+        seeds = [b"program-config"],
+        bump = program_config.bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        seeds = [b"tier-config", program_config.ofund_mint.as_ref()],
+        bump = tier_config.bump,
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Refresh a user's tier against the OFUND tier table
+#[derive(Accounts)]
+pub struct RefreshTier<'info> {
+    #[account(
+        mut,
+        seeds = [b"user-profile", user_profile.user.as_ref()],
+        bump = user_profile.bump,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(
+        seeds = [b"program-config"],
+        bump = program_config.bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        seeds = [b"tier-config", program_config.ofund_mint.as_ref()],
+        bump = tier_config.bump,
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+}
+
+// Configure a project's tier benefits
+#[derive(Accounts)]
+pub struct ConfigureTierBenefits<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"project", project.mint.as_ref(), &project.id.to_le_bytes()],
+        bump = project.bump,
+        has_one = authority @ OtonomError::Unauthorized,
+    )]
+    pub project: Account<'info, Project>,
 }
 
-// Initialize Mint Authority for New Token
+// Initialize the program config
 #[derive(Accounts)]
-pub struct InitializeMint<'info> {
+pub struct InitializeProgramConfig<'info> {
+    // Upgrade authority of this program
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub authority: Signer<'info>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::OtonomProgram>,
 
     #[account(
-        init,
-        payer = admin,
-        mint::decimals = 9,
-        mint::authority = mint_authority_pda,
+        constraint = program_data.upgrade_authority_address == Some(authority.key())
+            @ OtonomError::Unauthorized,
     )]
-    pub mint: Account<'info, Mint>,
+    pub program_data: Account<'info, ProgramData>,
 
+    // Mint authority of the OFUND mint
     #[account(
-        seeds = [b"mint-authority", mint.key().as_ref()],
-        bump,
+        seeds = [b"authority", mint_authority.mint.as_ref()],
+        bump = mint_authority.bump,
     )]
-    /// CHECK: PDA used as mint authority
-    pub mint_authority_pda: UncheckedAccount<'info>,
+    pub mint_authority: Account<'info, MintAuthority>,
 
     #[account(
         init,
-        payer = admin,
-        space = 8 + 1 + 32 + 32 + 4 + 32 + 4 + 16 + 4 + 128 + 1,
-        seeds = [b"authority", mint.key().as_ref()],
+        payer = authority,
+        space = PROGRAM_CONFIG_SPACE,
+        seeds = [b"program-config"],
         bump,
     )]
-    pub mint_authority: Account<'info, MintAuthority>,
+    pub program_config: Account<'info, ProgramConfig>,
 
-    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
 }
 
-// Initialize Mint Authority for Existing Mint
+// Initialize the tier table
 #[derive(Accounts)]
-pub struct InitializeExistingMint<'info> {
+pub struct InitializeTierConfig<'info> {
+    // Admin, or an admin multisig member co-signing with enough other members
     #[account(mut)]
     pub admin: Signer<'info>,
 
-    // Note: Not initializing the mint, just referencing it
-    #[account(mut)]
-    pub mint: Account<'info, Mint>,
-
     #[account(
-        seeds = [b"mint-authority", mint.key().as_ref()],
-        bump,
+        seeds = [b"authority", mint_authority.mint.as_ref()],
+        bump = mint_authority.bump,
     )]
-    /// CHECK: PDA used as mint authority
-    pub mint_authority_pda: UncheckedAccount<'info>,
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    // Required when the admin is an on-chain multisig
+    pub admin_multisig: Option<Account<'info, AdminMultisig>>,
 
     #[account(
         init,
         payer = admin,
-        space = 8 + 1 + 32 + 32 + 4 + 32 + 4 + 16 + 4 + 128 + 1,
-        seeds = [b"authority", mint.key().as_ref()],
+        space = TIER_CONFIG_SPACE,
+        seeds = [b"tier-config", mint_authority.mint.as_ref()],
         bump,
     )]
-    pub mint_authority: Account<'info, MintAuthority>,
+    pub tier_config: Account<'info, TierConfig>,
 
-    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
 }
 
-// Register User
+// Initialize the loyalty points config
 #[derive(Accounts)]
-pub struct RegisterUser<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
-
-    #[account(
-        init_if_needed,
-        payer = user,
-        space = USER_PROFILE_SPACE,
-        seeds = [b"user-profile", user.key().as_ref()],
-        bump,
-    )]
-    pub user_profile: Account<'info, UserProfile>,
-
+pub struct InitializePointsConfig<'info> {
+    // Admin, or an admin multisig member co-signing with enough other members
     #[account(mut)]
-    pub mint: Account<'info, Mint>,
-
-    #[account(
-        mut,
-        constraint = user_token_account.owner == user.key(),
-        constraint = user_token_account.mint == mint.key(),
-    )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub admin: Signer<'info>,
 
     #[account(
-        seeds = [b"authority", mint.key().as_ref()],
+        seeds = [b"authority", mint_authority.mint.as_ref()],
         bump = mint_authority.bump,
     )]
     pub mint_authority: Account<'info, MintAuthority>,
 
-    /// CHECK: PDA that is the mint authority
+    // Required when the admin is an on-chain multisig
+    pub admin_multisig: Option<Account<'info, AdminMultisig>>,
+
     #[account(
-        seeds = [b"mint-authority", mint.key().as_ref()],
-        bump = mint_authority.bump,
+        init,
+        payer = admin,
+        space = POINTS_CONFIG_SPACE,
+        seeds = [b"points-config", mint_authority.mint.as_ref()],
+        bump,
     )]
-    pub mint_authority_pda: UncheckedAccount<'info>,
+    pub points_config: Account<'info, PointsConfig>,
 
-    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
 }
 
-// Initialize a new project
+// Claim a contribution's loyalty points
 #[derive(Accounts)]
-#[instruction(project_name: String, project_bump: u8)]
-pub struct InitializeProject<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
+pub struct ClaimLoyaltyPoints<'info> {
+    /// CHECK: may already be closed; its status is read in the handler
+    #[account(address = contribution.project)]
+    pub project: UncheckedAccount<'info>,
 
     #[account(
-        init,
-        payer = authority,
-        space = 8 + 4 + 50 + 1 + 32 + 32 + 8, // Space for account data
-        seeds = [b"project", project_name.as_bytes()],
-        bump,
+        mut,
+        seeds = [b"contribution", contribution.project.as_ref(), contribution.user.as_ref()],
+        bump = contribution.bump,
     )]
-    pub project: Account<'info, Project>,
+    pub contribution: Account<'info, Contribution>,
 
     #[account(
         mut,
-        constraint = project_vault.owner == authority.key(),
+        seeds = [b"user-profile", contribution.user.as_ref()],
+        bump = user_profile.bump,
     )]
-    pub project_vault: Account<'info, TokenAccount>,
-
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
+    pub user_profile: Account<'info, UserProfile>,
 }
 
-// Invest in a project
+// Close a contribution
 #[derive(Accounts)]
-pub struct InvestInProject<'info> {
+pub struct CloseContribution<'info> {
     #[account(mut)]
-    pub investor: Signer<'info>,
+    pub user: Signer<'info>,
 
-    #[account(
-        mut,
-        seeds = [b"user-profile", investor.key().as_ref()],
-        bump = user_profile.bump,
-    )]
-    pub user_profile: Account<'info, UserProfile>,
+    /// CHECK: may already be closed; its status is read in the handler
+    #[account(address = contribution.project)]
+    pub project: UncheckedAccount<'info>,
 
     #[account(
         mut,
-        seeds = [b"project", project.name.as_bytes()],
-        bump = project.bump,
+        seeds = [b"contribution", contribution.project.as_ref(), user.key().as_ref()],
+        bump = contribution.bump,
+        has_one = user @ OtonomError::Unauthorized,
+        close = user,
     )]
-    pub project: Account<'info, Project>,
+    pub contribution: Account<'info, Contribution>,
+}
+
+// Close a lottery ticket
+#[derive(Accounts)]
+pub struct CloseLotteryTicket<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: may already be closed; its status is read in the handler
+    #[account(address = lottery_ticket.project)]
+    pub project: UncheckedAccount<'info>,
 
     #[account(
         mut,
-        constraint = investor_token_account.owner == investor.key(),
-        constraint = investor_token_account.mint == mint.key(),
+        seeds = [b"lottery-ticket", lottery_ticket.project.as_ref(), user.key().as_ref()],
+        bump = lottery_ticket.bump,
+        has_one = user @ OtonomError::Unauthorized,
+        close = user,
     )]
-    pub investor_token_account: Account<'info, TokenAccount>,
+    pub lottery_ticket: Account<'info, LotteryTicket>,
+}
+
+// Close a tier snapshot
+#[derive(Accounts)]
+pub struct CloseTierSnapshot<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: may already be closed; its status is read in the handler
+    #[account(address = tier_snapshot.project)]
+    pub project: UncheckedAccount<'info>,
 
     #[account(
         mut,
-        constraint = project_vault.owner == project.authority,
-        constraint = project_vault.mint == mint.key(),
+        seeds = [b"tier-snapshot", tier_snapshot.project.as_ref(), user.key().as_ref()],
+        bump = tier_snapshot.bump,
+        has_one = user @ OtonomError::Unauthorized,
+        close = user,
     )]
-    pub project_vault: Account<'info, TokenAccount>,
-
-    pub mint: Account<'info, Mint>,
-
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
+    pub tier_snapshot: Account<'info, TierSnapshot>,
 }
 
 // Mint Authority Account
@@ -387,16 +4780,265 @@ pub struct UserProfile {
     pub tier: u8,
     pub total_invested: u64,
     pub investments: Vec<Investment>,
+    pub currency_totals: Vec<CurrencyTotal>,
+    pub referrer: Option<Pubkey>,
+    pub referral_count: u32,
+    pub referral_rewards: u64,
+    pub tier_history: Vec<TierChange>,
+    pub loyalty_points: u64,
 }
 
 // Project Account
 #[account]
 pub struct Project {
+    pub id: u64,
     pub name: String,
     pub bump: u8,
     pub authority: Pubkey,
     pub vault: Pubkey,
     pub total_raised: u64,
+    pub mint: Pubkey,
+    pub payment_mint: Pubkey,
+    pub status: ProjectStatus,
+    pub has_lottery: bool,
+    pub sale_mode: SaleMode,
+    pub hard_cap: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub proceeds_withdrawn: u64,
+    pub unsettled_contributions: u32,
+    pub tier_snapshot_time: i64,
+    pub min_tier_hold: i64,
+    pub requires_kyc: bool,
+    pub metadata: ProjectMetadata,
+    pub pending_authority: Option<Pubkey>,
+    pub co_owners: Vec<Pubkey>,
+    pub min_investment: u64,
+    pub max_investment: u64,
+    pub exit_penalty_bps: u16,
+    pub exit_penalty_treasury: Pubkey,
+    pub tier_benefits: Vec<TierBenefit>,
+    pub open_contributions: u32,
+    pub has_deposits: bool,
+    pub max_allocation: u64,
+    pub paused: bool,
+}
+
+// Merkle Distributor Account
+#[account]
+pub struct Distributor {
+    pub bump: u8,
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub distributor_id: u64,
+    pub merkle_root: [u8; 32],
+    pub max_total_claim: u64,
+    pub max_num_nodes: u64,
+    pub total_amount_claimed: u64,
+    pub num_nodes_claimed: u64,
+    pub claimed_bitmap: Vec<u8>,
+}
+
+// Lottery Account for oversubscribed raises
+#[account]
+pub struct Lottery {
+    pub bump: u8,
+    pub project: Pubkey,
+    pub registration_end: i64,
+    pub investment_end: i64,
+    pub winning_tickets: u64,
+    pub allocation_per_ticket: u64,
+    pub total_tickets: u64,
+    pub commit_slot: u64,
+    pub seed: [u8; 32],
+    pub drawn: bool,
+}
+
+// Lottery Ticket Account, one per user per project
+#[account]
+pub struct LotteryTicket {
+    pub bump: u8,
+    pub project: Pubkey,
+    pub user: Pubkey,
+    pub first_ticket: u64,
+    pub ticket_count: u64,
+    pub invested: u64,
+    pub wins: Option<u64>,
+}
+
+// Contribution Account, one per user per project
+#[account]
+pub struct Contribution {
+    pub bump: u8,
+    pub project: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub accepted: u64,
+    pub settled: bool,
+    pub refunded: bool,
+    pub points_earned: u64,
+}
+
+// Tier Snapshot Account, a user's frozen tier for one project
+#[account]
+pub struct TierSnapshot {
+    pub bump: u8,
+    pub project: Pubkey,
+    pub user: Pubkey,
+    pub tier: u8,
+    pub snapshot_time: i64,
+}
+
+// Roles Account, delegating admin permissions to multiple keys
+#[account]
+pub struct Roles {
+    pub bump: u8,
+    pub mint: Pubkey,
+    pub members: Vec<RoleGrant>,
+}
+
+// Pending Action Account, a timelocked admin change
+#[account]
+pub struct PendingAction {
+    pub bump: u8,
+    pub mint: Pubkey,
+    pub action_id: u64,
+    pub proposer: Pubkey,
+    pub action: AdminAction,
+    pub queued_at: i64,
+    pub eta: i64,
+    pub approvals: Vec<Pubkey>,
+}
+
+// Admin Multisig Account, an on-chain m-of-n admin
+#[account]
+pub struct AdminMultisig {
+    pub bump: u8,
+    pub mint: Pubkey,
+    pub create_key: Pubkey,
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+// KYC Config Account, holding the authorized attester
+#[account]
+pub struct KycConfig {
+    pub bump: u8,
+    pub mint: Pubkey,
+    pub attester: Pubkey,
+}
+
+// Attestation Account, a user's KYC status
+#[account]
+pub struct Attestation {
+    pub bump: u8,
+    pub mint: Pubkey,
+    pub user: Pubkey,
+    pub attester: Pubkey,
+    pub issued_at: i64,
+    pub expires_at: i64,
+    pub revoked: bool,
+}
+
+// Registration Account, a permanent per-wallet marker that the grant was paid
+#[account]
+pub struct Registration {
+    pub bump: u8,
+    pub user: Pubkey,
+    pub registered_at: i64,
+}
+
+// Blocklist Entry Account, one per blocked wallet
+#[account]
+pub struct BlocklistEntry {
+    pub bump: u8,
+    pub mint: Pubkey,
+    pub wallet: Pubkey,
+    pub added_at: i64,
+}
+
+// Emitted when a wallet is added to the blocklist
+#[event]
+pub struct BlocklistAdded {
+    pub mint: Pubkey,
+    pub wallet: Pubkey,
+    pub timestamp: i64,
+}
+
+// Emitted when a wallet is removed from the blocklist
+#[event]
+pub struct BlocklistRemoved {
+    pub mint: Pubkey,
+    pub wallet: Pubkey,
+    pub timestamp: i64,
+}
+
+// Registration Limiter Account, a global per-window registration counter
+#[account]
+pub struct RegistrationLimiter {
+    pub bump: u8,
+    pub mint: Pubkey,
+    pub window_duration: i64,
+    pub max_registrations_per_window: u32,
+    pub registration_fee: u64,
+    pub treasury: Pubkey,
+    pub window_start: i64,
+    pub registrations_in_window: u32,
+}
+
+// Global Stats Account, platform totals readable in a single RPC call
+#[account]
+pub struct GlobalStats {
+    pub bump: u8,
+    pub mint: Pubkey,
+    pub user_count: u64,
+    pub project_count: u64,
+    pub total_ofund_raised: u64,
+    pub total_granted: u64,
+    pub total_fees: u64,
+    pub total_ofund_penalties: u64,
+}
+
+// Project Name Account, reserving a display name for one project
+#[account]
+pub struct ProjectName {
+    pub bump: u8,
+    pub project: Pubkey,
+}
+
+// Program Config Account, naming the OFUND mint whose tier table applies
+#[account]
+pub struct ProgramConfig {
+    pub bump: u8,
+    pub ofund_mint: Pubkey,
+}
+
+// Tier Config Account, the tier table sorted by ascending threshold
+#[account]
+pub struct TierConfig {
+    pub bump: u8,
+    pub mint: Pubkey,
+    pub tiers: Vec<TierLevel>,
+}
+
+// Loyalty points per whole token invested in one payment mint
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PointsRate {
+    pub mint: Pubkey,
+    pub points_per_token: u64,
+}
+
+// Points Config Account, loyalty point accrual and redemption rates. Rates are set
+// per payment mint, as whole tokens of different mints are worth different amounts;
+// mints without a rate earn no points. `points_per_lottery_ticket` of 0 disables
+// bonus lottery tickets.
+#[account]
+pub struct PointsConfig {
+    pub bump: u8,
+    pub mint: Pubkey,
+    pub rates: Vec<PointsRate>,
+    pub points_per_lottery_ticket: u64,
+    pub max_bonus_tickets: u64,
 }
 
 // Error definitions for more robust error handling
@@ -410,4 +5052,392 @@ pub enum OtonomError {
     MintAuthorityNotInitialized,
     #[msg("Arithmetic overflow occurred")]
     ArithmeticOverflow,
+    #[msg("Distributor size must be between 1 and the maximum number of claims")]
+    InvalidDistributorSize,
+    #[msg("Claim index is out of range for this distributor")]
+    InvalidClaimIndex,
+    #[msg("Airdrop allocation has already been claimed")]
+    AlreadyClaimed,
+    #[msg("Invalid Merkle proof")]
+    InvalidProof,
+    #[msg("Claim exceeds the distributor's maximum total")]
+    ExceededMaxClaim,
+    #[msg("Maximum number of payment currencies reached")]
+    MaxCurrenciesReached,
+    #[msg("Project is not active")]
+    ProjectNotActive,
+    #[msg("Project has not been settled")]
+    ProjectNotSettled,
+    #[msg("Project vault still holds tokens")]
+    VaultNotEmpty,
+    #[msg("Project account for an investment was not provided")]
+    MissingProjectAccount,
+    #[msg("User still has open investment positions")]
+    OpenPositionsRemain,
+    #[msg("User is already registered")]
+    AlreadyRegistered,
+    #[msg("Users cannot refer themselves")]
+    SelfReferral,
+    #[msg("Invalid lottery configuration")]
+    InvalidLotteryConfig,
+    #[msg("Lottery registration is closed")]
+    LotteryRegistrationClosed,
+    #[msg("Lottery registration is still open")]
+    LotteryRegistrationOpen,
+    #[msg("Lottery draw has already been committed")]
+    DrawAlreadyCommitted,
+    #[msg("Lottery draw has not been committed")]
+    DrawNotCommitted,
+    #[msg("Committed draw slot has not been reached")]
+    DrawSlotNotReached,
+    #[msg("Committed slot hash is no longer available; commit the draw again")]
+    SlotHashUnavailable,
+    #[msg("Lottery has already been drawn")]
+    LotteryAlreadyDrawn,
+    #[msg("Lottery has not been drawn")]
+    LotteryNotDrawn,
+    #[msg("A lottery ticket is required to invest in this project")]
+    LotteryTicketRequired,
+    #[msg("Lottery investment window has closed")]
+    LotteryWindowClosed,
+    #[msg("Investment exceeds the winning lottery allocation")]
+    LotteryAllocationExceeded,
+    #[msg("Invalid sale configuration")]
+    InvalidSaleConfig,
+    #[msg("Sale has already received deposits")]
+    SaleAlreadyStarted,
+    #[msg("Sale window has ended")]
+    SaleEnded,
+    #[msg("Sale window has not ended")]
+    SaleNotEnded,
+    #[msg("Investment exceeds the project hard cap")]
+    HardCapExceeded,
+    #[msg("Project vault must be owned by the project PDA")]
+    EscrowVaultRequired,
+    #[msg("Project is not an overflow sale")]
+    NotOverflowSale,
+    #[msg("Contribution has already been settled")]
+    ContributionAlreadySettled,
+    #[msg("Overflow contributions are still awaiting settlement")]
+    OverflowNotSettled,
+    #[msg("Amount exceeds the proceeds available to withdraw")]
+    InsufficientProceeds,
+    #[msg("Invalid tier snapshot configuration")]
+    InvalidSnapshotConfig,
+    #[msg("Project does not use tier snapshots")]
+    SnapshotNotConfigured,
+    #[msg("Tier snapshot time has not been reached")]
+    SnapshotTimeNotReached,
+    #[msg("A tier snapshot is required for this project")]
+    TierSnapshotRequired,
+    #[msg("Invalid role")]
+    InvalidRole,
+    #[msg("Member does not hold any roles")]
+    RoleNotFound,
+    #[msg("Maximum number of role members reached")]
+    MaxRoleMembersReached,
+    #[msg("Timelock delay is shorter than the minimum")]
+    TimelockTooShort,
+    #[msg("Timelock has not elapsed")]
+    TimelockNotElapsed,
+    #[msg("Queued action has expired")]
+    TimelockExpired,
+    #[msg("Roles account is required for this action")]
+    RolesAccountRequired,
+    #[msg("Invalid multisig configuration")]
+    InvalidMultisigConfig,
+    #[msg("Signer has already approved this action")]
+    AlreadyApproved,
+    #[msg("Action does not have enough approvals")]
+    InsufficientApprovals,
+    #[msg("KYC config account is required for this action")]
+    KycConfigRequired,
+    #[msg("A KYC attestation is required to invest in this project")]
+    AttestationRequired,
+    #[msg("KYC attestation has expired")]
+    AttestationExpired,
+    #[msg("KYC attestation has been revoked")]
+    AttestationRevoked,
+    #[msg("Wallet is blocked")]
+    WalletBlocked,
+    #[msg("Invalid registration limits")]
+    InvalidRegistrationLimits,
+    #[msg("Registration limiter account is required for this action")]
+    RegistrationLimiterRequired,
+    #[msg("Registration cap for the current window has been reached")]
+    RegistrationCapReached,
+    #[msg("Sale has not started")]
+    SaleNotStarted,
+    #[msg("Project metadata is locked once the raise opens")]
+    MetadataLocked,
+    #[msg("Project metadata field is too long")]
+    MetadataTooLong,
+    #[msg("Project name must be between 1 and 50 bytes")]
+    InvalidProjectName,
+    #[msg("Key is already an owner of this project")]
+    AlreadyCoOwner,
+    #[msg("Project has the maximum number of co-owners")]
+    TooManyCoOwners,
+    #[msg("Key is not a co-owner of this project")]
+    NotCoOwner,
+    #[msg("Project has been cancelled")]
+    ProjectCancelled,
+    #[msg("Project has not been cancelled")]
+    ProjectNotCancelled,
+    #[msg("Contribution has already been refunded")]
+    AlreadyRefunded,
+    #[msg("Proceeds have already been withdrawn from this project")]
+    ProceedsAlreadyWithdrawn,
+    #[msg("Investment amount must be greater than zero")]
+    ZeroInvestment,
+    #[msg("Investment is below the project's minimum ticket size")]
+    InvestmentBelowMinimum,
+    #[msg("Investment is above the project's maximum ticket size")]
+    InvestmentAboveMaximum,
+    #[msg("Minimum ticket size exceeds the maximum")]
+    InvalidTicketSize,
+    #[msg("Penalty must not exceed 10000 basis points")]
+    InvalidPenalty,
+    #[msg("Withdrawal exceeds the contributed amount")]
+    InsufficientContribution,
+    #[msg("Treasury token account is required for this action")]
+    TreasuryRequired,
+    #[msg("Invalid tier benefit configuration")]
+    InvalidTierBenefits,
+    #[msg("Early access for this tier has not opened yet")]
+    EarlyAccessNotOpen,
+    #[msg("Invalid tier level")]
+    InvalidTierLevel,
+    #[msg("Tier table is full")]
+    TooManyTiers,
+    #[msg("Tier level not found")]
+    TierLevelNotFound,
+    #[msg("Refund has not been claimed")]
+    RefundUnclaimed,
+    #[msg("Contribution account for a cancelled project was not provided")]
+    MissingContributionAccount,
+    #[msg("Project has no pending ownership transfer")]
+    NoPendingAuthority,
+    #[msg("Refunds are still owed to investors")]
+    RefundsOutstanding,
+    #[msg("Project sale has not been configured")]
+    SaleNotConfigured,
+    #[msg("Invalid loyalty points config")]
+    InvalidPointsConfig,
+    #[msg("Tier config account is required for this action")]
+    TierConfigRequired,
+    #[msg("Points config account is required for this action")]
+    PointsConfigRequired,
+    #[msg("KYC attestation was not issued by the current attester")]
+    AttesterMismatch,
+    #[msg("Investment exceeds the investor's allocation for this project")]
+    AllocationExceeded,
+    #[msg("Project deposits are paused")]
+    ProjectPaused,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash_pair(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+        if a <= b {
+            keccak::hashv(&[&a, &b]).0
+        } else {
+            keccak::hashv(&[&b, &a]).0
+        }
+    }
+
+    fn lottery(total_tickets: u64, winning_tickets: u64, seed: [u8; 32]) -> Lottery {
+        Lottery {
+            bump: 0,
+            project: Pubkey::default(),
+            registration_end: 0,
+            investment_end: 0,
+            winning_tickets,
+            allocation_per_ticket: 0,
+            total_tickets,
+            commit_slot: 0,
+            seed,
+            drawn: true,
+        }
+    }
+
+    fn user_profile(history: &[(u8, i64)]) -> UserProfile {
+        UserProfile {
+            user: Pubkey::default(),
+            bump: 0,
+            tier: history.last().map_or(0, |(tier, _)| *tier),
+            total_invested: 0,
+            investments: vec![],
+            currency_totals: vec![],
+            referrer: None,
+            referral_count: 0,
+            referral_rewards: 0,
+            tier_history: history
+                .iter()
+                .map(|(tier, timestamp)| TierChange {
+                    tier: *tier,
+                    timestamp: *timestamp,
+                })
+                .collect(),
+            loyalty_points: 0,
+        }
+    }
+
+    fn tier_config(thresholds: &[u64]) -> TierConfig {
+        TierConfig {
+            bump: 0,
+            mint: Pubkey::default(),
+            tiers: thresholds
+                .iter()
+                .enumerate()
+                .map(|(i, threshold)| TierLevel {
+                    threshold: *threshold,
+                    name: format!("Tier {}", i),
+                    lottery_tickets: i as u64 + 1,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn airdrop_leaf_layout() {
+        let claimant = Pubkey::new_unique();
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&7u64.to_le_bytes());
+        bytes.extend_from_slice(claimant.as_ref());
+        bytes.extend_from_slice(&1_000u64.to_le_bytes());
+        assert_eq!(airdrop_leaf(7, &claimant, 1_000), keccak::hash(&bytes).0);
+        assert_ne!(airdrop_leaf(7, &claimant, 1_000), airdrop_leaf(7, &claimant, 1_001));
+        assert_ne!(airdrop_leaf(7, &claimant, 1_000), airdrop_leaf(8, &claimant, 1_000));
+    }
+
+    #[test]
+    fn merkle_proof_verifies_every_leaf() {
+        let claimants: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let leaves: Vec<[u8; 32]> = claimants
+            .iter()
+            .enumerate()
+            .map(|(i, claimant)| airdrop_leaf(i as u64, claimant, 100 * (i as u64 + 1)))
+            .collect();
+        let left = hash_pair(leaves[0], leaves[1]);
+        let right = hash_pair(leaves[2], leaves[3]);
+        let root = hash_pair(left, right);
+
+        assert!(verify_merkle_proof(&[leaves[1], right], root, leaves[0]));
+        assert!(verify_merkle_proof(&[leaves[0], right], root, leaves[1]));
+        assert!(verify_merkle_proof(&[leaves[3], left], root, leaves[2]));
+        assert!(verify_merkle_proof(&[leaves[2], left], root, leaves[3]));
+
+        // Wrong amount, wrong sibling, or a missing level must fail
+        let forged = airdrop_leaf(0, &claimants[0], 101);
+        assert!(!verify_merkle_proof(&[leaves[1], right], root, forged));
+        assert!(!verify_merkle_proof(&[leaves[2], right], root, leaves[0]));
+        assert!(!verify_merkle_proof(&[leaves[1]], root, leaves[0]));
+        assert!(verify_merkle_proof(&[], root, root));
+    }
+
+    #[test]
+    fn find_slot_hash_reads_sysvar_entries() {
+        let entries: [(u64, [u8; 32]); 3] = [(42, [3u8; 32]), (41, [2u8; 32]), (40, [1u8; 32])];
+        let mut data = (entries.len() as u64).to_le_bytes().to_vec();
+        for (slot, hash) in entries {
+            data.extend_from_slice(&slot.to_le_bytes());
+            data.extend_from_slice(&hash);
+        }
+        // Trailing bytes past the declared length are ignored
+        data.extend_from_slice(&39u64.to_le_bytes());
+        data.extend_from_slice(&[9u8; 32]);
+
+        let key = anchor_lang::solana_program::sysvar::slot_hashes::ID;
+        let owner = anchor_lang::solana_program::sysvar::ID;
+        let mut lamports = 0;
+        let info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+
+        assert_eq!(find_slot_hash(&info, 42).unwrap(), [3u8; 32]);
+        assert_eq!(find_slot_hash(&info, 40).unwrap(), [1u8; 32]);
+        assert!(find_slot_hash(&info, 39).is_err());
+        assert!(find_slot_hash(&info, 43).is_err());
+    }
+
+    #[test]
+    fn lottery_draws_exactly_winning_tickets() {
+        for (total_tickets, winning_tickets) in [(1u64, 1u64), (2, 1), (10, 3), (97, 40), (1_000, 999), (5, 5)] {
+            for seed_byte in 0..4u8 {
+                let lottery = lottery(total_tickets, winning_tickets, [seed_byte; 32]);
+                let mut positions: Vec<u64> = (0..total_tickets)
+                    .map(|ticket| shuffle_ticket(&lottery.seed, ticket, total_tickets))
+                    .collect();
+                positions.sort_unstable();
+                assert!(positions.iter().copied().eq(0..total_tickets));
+
+                let winners = (0..total_tickets)
+                    .filter(|ticket| is_winning_ticket(&lottery, *ticket))
+                    .count() as u64;
+                assert_eq!(winners, winning_tickets);
+                assert_eq!(count_winning_tickets(&lottery, 0, total_tickets), winning_tickets);
+            }
+        }
+
+        // A different seed picks a different set of winners
+        let a = lottery(100, 10, [1u8; 32]);
+        let b = lottery(100, 10, [2u8; 32]);
+        assert!((0..100).any(|ticket| is_winning_ticket(&a, ticket) != is_winning_ticket(&b, ticket)));
+    }
+
+    #[test]
+    fn overflow_accepted_amount_covers_hard_cap() {
+        assert_eq!(overflow_accepted_amount(500, 1_000, 800).unwrap(), 500);
+        assert_eq!(overflow_accepted_amount(500, 1_000, 1_000).unwrap(), 500);
+        assert_eq!(overflow_accepted_amount(1_000, 1_000, 3_000).unwrap(), 334);
+
+        let deposits = [1u64, 7, 333, 1_000, 2_659];
+        let total_raised: u64 = deposits.iter().sum();
+        for hard_cap in [1u64, 10, 1_000, 3_999] {
+            let mut accepted_total = 0;
+            for deposited in deposits {
+                let accepted = overflow_accepted_amount(deposited, hard_cap, total_raised).unwrap();
+                assert!(accepted <= deposited);
+                accepted_total += accepted;
+            }
+            // Rounding up covers the hard cap by at most one unit per contribution
+            assert!(accepted_total >= hard_cap);
+            assert!(accepted_total - hard_cap < deposits.len() as u64);
+        }
+
+        assert_eq!(
+            overflow_accepted_amount(u64::MAX, u64::MAX - 1, u64::MAX).unwrap(),
+            u64::MAX - 1
+        );
+    }
+
+    #[test]
+    fn min_tier_held_uses_lowest_tier_in_window() {
+        let profile = user_profile(&[(1, 100), (3, 200), (2, 300), (4, 400)]);
+        assert_eq!(min_tier_held(&profile, 250, 350), 2);
+        assert_eq!(min_tier_held(&profile, 200, 299), 3);
+        assert_eq!(min_tier_held(&profile, 150, 450), 1);
+        assert_eq!(min_tier_held(&profile, 400, 500), 4);
+        // A change at the window start counts; one after the window does not
+        assert_eq!(min_tier_held(&profile, 300, 399), 2);
+        // Time before the first entry counts as tier 0
+        assert_eq!(min_tier_held(&profile, 50, 500), 0);
+        assert_eq!(min_tier_held(&user_profile(&[]), 0, 500), 0);
+    }
+
+    #[test]
+    fn calculate_tier_picks_highest_reached_level() {
+        let config = tier_config(&[0, 100, 1_000, 10_000]);
+        assert_eq!(calculate_tier(&config, 0), 0);
+        assert_eq!(calculate_tier(&config, 99), 0);
+        assert_eq!(calculate_tier(&config, 100), 1);
+        assert_eq!(calculate_tier(&config, 999), 1);
+        assert_eq!(calculate_tier(&config, 1_000), 2);
+        assert_eq!(calculate_tier(&config, 10_000), 3);
+        assert_eq!(calculate_tier(&config, u64::MAX), 3);
+
+        assert_eq!(calculate_tier(&tier_config(&[0]), u64::MAX), 0);
+        assert_eq!(calculate_tier(&tier_config(&[0, 1]), 1), 1);
+    }
 }
@@ -0,0 +1,291 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_lang::solana_program::instruction::AccountMeta;
+use common::{pda, program_error, Protocol};
+use ofund_token::{
+    accounts, instruction, AdminAction, MintAuthority, OtonomError, PointsConfig, PointsRate,
+    TierConfig, TierLevel,
+};
+
+const OFUND: u64 = 1_000_000_000;
+const TIMELOCK: i64 = 2 * 24 * 60 * 60;
+
+fn pending_action(protocol: &Protocol, action_id: u64) -> Pubkey {
+    pda(&[
+        b"pending-action",
+        protocol.mint.as_ref(),
+        &action_id.to_le_bytes(),
+    ])
+}
+
+fn queue(
+    protocol: &mut Protocol,
+    action_id: u64,
+    action: AdminAction,
+) -> std::result::Result<i64, ProgramError> {
+    let admin = protocol.admin;
+    queue_by(protocol, admin, None, action_id, action)
+}
+
+fn queue_by(
+    protocol: &mut Protocol,
+    proposer: Pubkey,
+    admin_multisig: Option<Pubkey>,
+    action_id: u64,
+    action: AdminAction,
+) -> std::result::Result<i64, ProgramError> {
+    let eta = protocol.env.now() + TIMELOCK;
+    let accounts = accounts::QueueAdminAction {
+        proposer,
+        mint_authority: protocol.mint_authority,
+        admin_multisig,
+        pending_action: pending_action(protocol, action_id),
+        system_program: system_program::ID,
+    };
+    protocol.env.process(
+        accounts,
+        instruction::QueueAdminAction {
+            action_id,
+            action,
+            eta,
+        },
+    )?;
+    Ok(eta)
+}
+
+fn execute(
+    protocol: &mut Protocol,
+    action_id: u64,
+    tier_config: Option<Pubkey>,
+    points_config: Option<Pubkey>,
+) -> std::result::Result<(), ProgramError> {
+    let admin = protocol.admin;
+    execute_by(protocol, action_id, admin, None, tier_config, points_config)
+}
+
+fn execute_by(
+    protocol: &mut Protocol,
+    action_id: u64,
+    proposer: Pubkey,
+    admin_multisig: Option<Pubkey>,
+    tier_config: Option<Pubkey>,
+    points_config: Option<Pubkey>,
+) -> std::result::Result<(), ProgramError> {
+    let accounts = accounts::ExecuteAdminAction {
+        mint_authority: protocol.mint_authority,
+        pending_action: pending_action(protocol, action_id),
+        proposer,
+        admin_multisig,
+        roles: None,
+        kyc_config: None,
+        registration_limiter: None,
+        tier_config,
+        points_config,
+    };
+    protocol
+        .env
+        .process(accounts, instruction::ExecuteAdminAction {})
+}
+
+fn level(name: &str, threshold: u64) -> TierLevel {
+    TierLevel {
+        threshold,
+        name: name.to_string(),
+        lottery_tickets: 8,
+    }
+}
+
+#[test]
+fn tier_level_changes_wait_for_the_timelock() {
+    let mut protocol = Protocol::new();
+    let tier_config = protocol.tier_config();
+
+    assert_eq!(
+        queue(&mut protocol, 1, AdminAction::AddTierLevel { level: level("", 1) }),
+        Err(program_error(OtonomError::InvalidTierLevel))
+    );
+    let mut crowded = level("Crowded", 1_000_000 * OFUND);
+    crowded.lottery_tickets = 65;
+    assert_eq!(
+        queue(&mut protocol, 1, AdminAction::AddTierLevel { level: crowded }),
+        Err(program_error(OtonomError::InvalidTierLevel))
+    );
+    let platinum = level("Platinum", 1_000_000 * OFUND);
+    let eta = queue(&mut protocol, 1, AdminAction::AddTierLevel { level: platinum }).unwrap();
+
+    assert_eq!(
+        execute(&mut protocol, 1, Some(tier_config), None),
+        Err(program_error(OtonomError::TimelockNotElapsed))
+    );
+    protocol.env.set_time(eta);
+    assert_eq!(
+        execute(&mut protocol, 1, None, None),
+        Err(program_error(OtonomError::TierConfigRequired))
+    );
+    execute(&mut protocol, 1, Some(tier_config), None).unwrap();
+
+    let config: TierConfig = protocol.env.state(&tier_config);
+    assert_eq!(config.tiers.len(), 5);
+    assert_eq!(config.tiers[4].name, "Platinum");
+    assert!(!protocol.env.exists(&pending_action(&protocol, 1)));
+}
+
+#[test]
+fn tier_level_order_is_checked_when_the_change_executes() {
+    let mut protocol = Protocol::new();
+    let tier_config = protocol.tier_config();
+
+    // Both fit the table as queued, but not once the other has run
+    let eta = queue(&mut protocol, 1, AdminAction::RemoveTierLevel).unwrap();
+    let gold = level("Gold+", 200_000 * OFUND);
+    queue(&mut protocol, 2, AdminAction::UpdateTierLevel { index: 3, level: gold }).unwrap();
+
+    protocol.env.set_time(eta);
+    execute(&mut protocol, 1, Some(tier_config), None).unwrap();
+    assert_eq!(
+        execute(&mut protocol, 2, Some(tier_config), None),
+        Err(program_error(OtonomError::TierLevelNotFound))
+    );
+
+    let config: TierConfig = protocol.env.state(&tier_config);
+    assert_eq!(config.tiers.len(), 3);
+}
+
+#[test]
+fn points_config_changes_wait_for_the_timelock() {
+    let mut protocol = Protocol::new();
+    let points_config = protocol.points_config();
+    let usdc = Pubkey::new_unique();
+    let rate = |mint| PointsRate {
+        mint,
+        points_per_token: 2,
+    };
+
+    let duplicated = AdminAction::UpdatePointsConfig {
+        rates: vec![rate(usdc), rate(usdc)],
+        points_per_lottery_ticket: 500,
+        max_bonus_tickets: 3,
+    };
+    assert_eq!(
+        queue(&mut protocol, 1, duplicated),
+        Err(program_error(OtonomError::InvalidPointsConfig))
+    );
+
+    let update = AdminAction::UpdatePointsConfig {
+        rates: vec![rate(protocol.mint), rate(usdc)],
+        points_per_lottery_ticket: 500,
+        max_bonus_tickets: 3,
+    };
+    let eta = queue(&mut protocol, 1, update).unwrap();
+    assert_eq!(
+        execute(&mut protocol, 1, None, Some(points_config)),
+        Err(program_error(OtonomError::TimelockNotElapsed))
+    );
+    protocol.env.set_time(eta);
+    assert_eq!(
+        execute(&mut protocol, 1, None, None),
+        Err(program_error(OtonomError::PointsConfigRequired))
+    );
+    execute(&mut protocol, 1, None, Some(points_config)).unwrap();
+
+    let config: PointsConfig = protocol.env.state(&points_config);
+    assert_eq!(config.rates.len(), 2);
+    assert_eq!(config.rates[1].mint, usdc);
+    assert_eq!(config.points_per_lottery_ticket, 500);
+    assert_eq!(config.max_bonus_tickets, 3);
+}
+
+#[test]
+fn multisig_admin_needs_threshold_signatures() {
+    let mut protocol = Protocol::new();
+    let (admin, mint_authority) = (protocol.admin, protocol.mint_authority);
+    let members = [
+        protocol.env.wallet(),
+        protocol.env.wallet(),
+        protocol.env.wallet(),
+    ];
+    let outsider = protocol.env.wallet();
+
+    // A 2-of-3 multisig takes over as admin
+    let create_key = Pubkey::new_unique();
+    let multisig = pda(&[b"admin-multisig", protocol.mint.as_ref(), create_key.as_ref()]);
+    protocol
+        .env
+        .process(
+            accounts::InitializeAdminMultisig {
+                admin,
+                mint_authority,
+                current_admin_multisig: None,
+                admin_multisig: multisig,
+                system_program: system_program::ID,
+            },
+            instruction::InitializeAdminMultisig {
+                create_key,
+                signers: members.to_vec(),
+                threshold: 2,
+            },
+        )
+        .unwrap();
+    let eta = queue(&mut protocol, 1, AdminAction::TransferAdmin { new_admin: multisig }).unwrap();
+    protocol.env.set_time(eta);
+    execute(&mut protocol, 1, None, None).unwrap();
+
+    // Direct instructions: the `admin` signer plus co-signers in remaining accounts
+    let wallet = Pubkey::new_unique();
+    let block = |protocol: &mut Protocol, co_signers: &[Pubkey]| {
+        let remaining: Vec<AccountMeta> = co_signers
+            .iter()
+            .map(|key| AccountMeta::new_readonly(*key, true))
+            .collect();
+        protocol.env.process_with_remaining(
+            accounts::AddToBlocklist {
+                admin: members[0],
+                mint_authority,
+                admin_multisig: Some(multisig),
+                blocklist_entry: pda(&[b"blocklist", protocol.mint.as_ref(), wallet.as_ref()]),
+                system_program: system_program::ID,
+            },
+            &remaining,
+            instruction::AddToBlocklist { wallet },
+        )
+    };
+    for co_signers in [vec![], vec![members[0]], vec![outsider]] {
+        assert_eq!(
+            block(&mut protocol, &co_signers),
+            Err(program_error(OtonomError::Unauthorized))
+        );
+    }
+    block(&mut protocol, &[members[2]]).unwrap();
+
+    // Queued actions: approvals recorded on the pending action
+    let eta = queue_by(
+        &mut protocol,
+        members[1],
+        Some(multisig),
+        2,
+        AdminAction::TransferAdmin { new_admin: admin },
+    )
+    .unwrap();
+    protocol.env.set_time(eta);
+    assert_eq!(
+        execute_by(&mut protocol, 2, members[1], Some(multisig), None, None),
+        Err(program_error(OtonomError::InsufficientApprovals))
+    );
+    protocol
+        .env
+        .process(
+            accounts::ApproveAdminAction {
+                approver: members[0],
+                mint_authority,
+                admin_multisig: multisig,
+                pending_action: pending_action(&protocol, 2),
+            },
+            instruction::ApproveAdminAction {},
+        )
+        .unwrap();
+    execute_by(&mut protocol, 2, members[1], Some(multisig), None, None).unwrap();
+    let state: MintAuthority = protocol.env.state(&mint_authority);
+    assert_eq!(state.admin, admin);
+}
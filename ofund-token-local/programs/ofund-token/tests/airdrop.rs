@@ -0,0 +1,157 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{keccak, system_program};
+use anchor_spl::token::spl_token;
+use common::{pda, program_error, Protocol};
+use ofund_token::{accounts, instruction, Distributor, GlobalStats, OtonomError};
+
+fn leaf(index: u64, claimant: &Pubkey, amount: u64) -> [u8; 32] {
+    keccak::hashv(&[&index.to_le_bytes(), claimant.as_ref(), &amount.to_le_bytes()]).0
+}
+
+fn hash_pair(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+    if a <= b {
+        keccak::hashv(&[&a, &b]).0
+    } else {
+        keccak::hashv(&[&b, &a]).0
+    }
+}
+
+fn initialize_distributor(
+    protocol: &mut Protocol,
+    distributor_id: u64,
+    merkle_root: [u8; 32],
+    max_total_claim: u64,
+    max_num_nodes: u64,
+) -> std::result::Result<Pubkey, ProgramError> {
+    let distributor = pda(&[
+        b"distributor",
+        protocol.mint.as_ref(),
+        &distributor_id.to_le_bytes(),
+    ]);
+    protocol.env.process(
+        accounts::InitializeDistributor {
+            operator: protocol.admin,
+            mint_authority: protocol.mint_authority,
+            roles: None,
+            mint: protocol.mint,
+            distributor,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeDistributor {
+            distributor_id,
+            merkle_root,
+            max_total_claim,
+            max_num_nodes,
+        },
+    )?;
+    Ok(distributor)
+}
+
+fn claim(
+    protocol: &mut Protocol,
+    distributor: Pubkey,
+    claimant: Pubkey,
+    claimant_token_account: Pubkey,
+    index: u64,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> std::result::Result<(), ProgramError> {
+    let (mint, global_stats, mint_authority_pda) = (
+        protocol.mint,
+        protocol.global_stats(),
+        protocol.mint_authority_pda,
+    );
+    protocol.env.process(
+        accounts::ClaimAirdrop {
+            claimant,
+            distributor,
+            mint,
+            claimant_token_account,
+            global_stats,
+            mint_authority_pda,
+            token_program: spl_token::ID,
+        },
+        instruction::ClaimAirdrop {
+            index,
+            amount,
+            proof,
+        },
+    )
+}
+
+#[test]
+fn claim_airdrop_mints_each_leaf_once() {
+    let mut protocol = Protocol::new();
+    let alice = protocol.env.wallet();
+    let bob = protocol.env.wallet();
+    let mint = protocol.mint;
+    let alice_tokens = protocol.env.create_token_account(&mint, &alice, 0);
+
+    let alice_leaf = leaf(0, &alice, 500);
+    let bob_leaf = leaf(1, &bob, 700);
+    let root = hash_pair(alice_leaf, bob_leaf);
+    let distributor = initialize_distributor(&mut protocol, 7, root, 1_200, 2).unwrap();
+
+    // A proof for an amount other than the leaf's is rejected
+    assert_eq!(
+        claim(&mut protocol, distributor, alice, alice_tokens, 0, 900, vec![bob_leaf]),
+        Err(program_error(OtonomError::InvalidProof))
+    );
+
+    claim(&mut protocol, distributor, alice, alice_tokens, 0, 500, vec![bob_leaf]).unwrap();
+    assert_eq!(protocol.env.token_balance(&alice_tokens), 500);
+
+    let state: Distributor = protocol.env.state(&distributor);
+    assert_eq!(state.total_amount_claimed, 500);
+    assert_eq!(state.num_nodes_claimed, 1);
+    assert_eq!(state.claimed_bitmap, vec![0b01]);
+    let stats: GlobalStats = protocol.env.state(&protocol.global_stats());
+    assert_eq!(stats.total_granted, 500);
+
+    assert_eq!(
+        claim(&mut protocol, distributor, alice, alice_tokens, 0, 500, vec![bob_leaf]),
+        Err(program_error(OtonomError::AlreadyClaimed))
+    );
+    assert_eq!(protocol.env.token_balance(&alice_tokens), 500);
+}
+
+#[test]
+fn claim_airdrop_respects_max_total_claim() {
+    let mut protocol = Protocol::new();
+    let alice = protocol.env.wallet();
+    let mint = protocol.mint;
+    let alice_tokens = protocol.env.create_token_account(&mint, &alice, 0);
+
+    let alice_leaf = leaf(0, &alice, 500);
+    let distributor = initialize_distributor(&mut protocol, 1, alice_leaf, 400, 1).unwrap();
+
+    assert_eq!(
+        claim(&mut protocol, distributor, alice, alice_tokens, 0, 500, vec![]),
+        Err(program_error(OtonomError::ExceededMaxClaim))
+    );
+    assert_eq!(
+        claim(&mut protocol, distributor, alice, alice_tokens, 1, 500, vec![]),
+        Err(program_error(OtonomError::InvalidClaimIndex))
+    );
+    assert_eq!(protocol.env.mint_supply(&mint), 0);
+}
+
+#[test]
+fn initialize_distributor_rejects_sizes_out_of_range() {
+    let mut protocol = Protocol::new();
+    let distributor = pda(&[b"distributor", protocol.mint.as_ref(), &3u64.to_le_bytes()]);
+
+    for max_num_nodes in [0, 80_001, u64::MAX] {
+        assert_eq!(
+            initialize_distributor(&mut protocol, 3, [1; 32], 1_000, max_num_nodes),
+            Err(program_error(OtonomError::InvalidDistributorSize))
+        );
+        assert!(!protocol.env.exists(&distributor));
+    }
+
+    initialize_distributor(&mut protocol, 3, [1; 32], 1_000, 80_000).unwrap();
+    let state: Distributor = protocol.env.state(&distributor);
+    assert_eq!(state.claimed_bitmap.len(), 10_000);
+}
@@ -0,0 +1,803 @@
+// In-process harness for running instruction handlers without a validator.
+//
+// Accounts live in leaked buffers laid out like the runtime's serialized input,
+// so `realloc` and `assign` behave as they do on chain. CPIs are routed through
+// the syscall stubs below: SPL Token instructions run the real token processor
+// and system program instructions are emulated.
+#![allow(dead_code)]
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::Once;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    bpf_loader_upgradeable,
+    entrypoint::{ProgramResult, MAX_PERMITTED_DATA_INCREASE, SUCCESS},
+    instruction::Instruction,
+    native_token::LAMPORTS_PER_SOL,
+    program_option::COption,
+    program_pack::Pack,
+    program_stubs::{self, SyscallStubs},
+    system_program, sysvar,
+};
+use anchor_lang::{AccountDeserialize, AccountSerialize, InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token;
+
+thread_local! {
+    static CLOCK: RefCell<Clock> = RefCell::new(Clock::default());
+}
+
+// Key followed by the original data length the runtime stores in front of it;
+// `AccountInfo::realloc` reads that length from the four bytes before the key
+#[repr(C)]
+struct KeyCell {
+    _padding: u32,
+    original_data_len: u32,
+    key: Pubkey,
+}
+
+struct Snapshot {
+    lamports: u64,
+    owner: Pubkey,
+    data: Vec<u8>,
+}
+
+pub struct Env {
+    accounts: HashMap<Pubkey, AccountInfo<'static>>,
+}
+
+impl Env {
+    pub fn new() -> Self {
+        static STUBS: Once = Once::new();
+        STUBS.call_once(|| {
+            program_stubs::set_syscall_stubs(Box::new(Stubs));
+        });
+        CLOCK.with(|clock| *clock.borrow_mut() = Clock::default());
+
+        let mut env = Env {
+            accounts: HashMap::new(),
+        };
+        // Upgradeable loader state: Program { programdata_address }
+        let mut program_data = 2u32.to_le_bytes().to_vec();
+        program_data.extend_from_slice(env.program_data().as_ref());
+        env.set_account(ofund_token::ID, 1, bpf_loader_upgradeable::ID, &program_data, true);
+        env.set_upgrade_authority(None);
+        env.set_account(spl_token::ID, 1, bpf_loader_upgradeable::ID, &[], true);
+        env.set_account(system_program::ID, 1, Pubkey::default(), &[], true);
+
+        let rent = Rent::default();
+        let mut rent_data = Vec::new();
+        rent_data.extend_from_slice(&rent.lamports_per_byte_year.to_le_bytes());
+        rent_data.extend_from_slice(&rent.exemption_threshold.to_le_bytes());
+        rent_data.push(rent.burn_percent);
+        env.set_account(sysvar::rent::ID, 1, sysvar::ID, &rent_data, false);
+        env
+    }
+
+    pub fn program_data(&self) -> Pubkey {
+        Pubkey::find_program_address(&[ofund_token::ID.as_ref()], &bpf_loader_upgradeable::ID).0
+    }
+
+    pub fn set_upgrade_authority(&mut self, authority: Option<Pubkey>) {
+        // Upgradeable loader state: ProgramData { slot, upgrade_authority_address }
+        let mut data = 3u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&0u64.to_le_bytes());
+        match authority {
+            Some(authority) => {
+                data.push(1);
+                data.extend_from_slice(authority.as_ref());
+            }
+            None => data.push(0),
+        }
+        let program_data = self.program_data();
+        self.set_account(program_data, 1, bpf_loader_upgradeable::ID, &data, false);
+    }
+
+    // Set the unix timestamp (and a matching slot) returned by Clock::get()
+    pub fn set_time(&self, unix_timestamp: i64) {
+        CLOCK.with(|clock| {
+            let mut clock = clock.borrow_mut();
+            clock.unix_timestamp = unix_timestamp;
+            clock.slot = unix_timestamp.max(0) as u64;
+        });
+    }
+
+    pub fn now(&self) -> i64 {
+        CLOCK.with(|clock| clock.borrow().unix_timestamp)
+    }
+
+    pub fn set_account(
+        &mut self,
+        key: Pubkey,
+        lamports: u64,
+        owner: Pubkey,
+        data: &[u8],
+        executable: bool,
+    ) {
+        let cell: &'static mut KeyCell = Box::leak(Box::new(KeyCell {
+            _padding: 0,
+            original_data_len: data.len() as u32,
+            key,
+        }));
+        // Length header, data, then room to grow by the runtime's realloc limit
+        let words = (8 + data.len() + MAX_PERMITTED_DATA_INCREASE) / 8 + 1;
+        let buffer = Box::leak(vec![0u64; words].into_boxed_slice());
+        let base = buffer.as_mut_ptr() as *mut u8;
+        let data_slice: &'static mut [u8] = unsafe {
+            *(base as *mut u64) = data.len() as u64;
+            std::slice::from_raw_parts_mut(base.add(8), data.len())
+        };
+        data_slice.copy_from_slice(data);
+
+        let info = AccountInfo::new(
+            &cell.key,
+            false,
+            true,
+            Box::leak(Box::new(lamports)),
+            data_slice,
+            Box::leak(Box::new(owner)),
+            executable,
+            0,
+        );
+        self.accounts.insert(key, info);
+    }
+
+    // A system-owned wallet funded with 100 SOL
+    pub fn wallet(&mut self) -> Pubkey {
+        let key = Pubkey::new_unique();
+        self.set_account(key, 100 * LAMPORTS_PER_SOL, system_program::ID, &[], false);
+        key
+    }
+
+    pub fn create_mint(&mut self, authority: &Pubkey, decimals: u8) -> Pubkey {
+        let key = Pubkey::new_unique();
+        let mut data = vec![0u8; spl_token::state::Mint::LEN];
+        spl_token::state::Mint {
+            mint_authority: COption::Some(*authority),
+            supply: 0,
+            decimals,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        }
+        .pack_into_slice(&mut data);
+        let lamports = Rent::default().minimum_balance(data.len());
+        self.set_account(key, lamports, spl_token::ID, &data, false);
+        key
+    }
+
+    pub fn create_token_account(&mut self, mint: &Pubkey, owner: &Pubkey, amount: u64) -> Pubkey {
+        let key = Pubkey::new_unique();
+        let mut data = vec![0u8; spl_token::state::Account::LEN];
+        spl_token::state::Account {
+            mint: *mint,
+            owner: *owner,
+            amount,
+            delegate: COption::None,
+            state: spl_token::state::AccountState::Initialized,
+            is_native: COption::None,
+            delegated_amount: 0,
+            close_authority: COption::None,
+        }
+        .pack_into_slice(&mut data);
+        let lamports = Rent::default().minimum_balance(data.len());
+        self.set_account(key, lamports, spl_token::ID, &data, false);
+        key
+    }
+
+    pub fn token_balance(&self, key: &Pubkey) -> u64 {
+        let info = &self.accounts[key];
+        let data = info.data.borrow();
+        spl_token::state::Account::unpack(&data).unwrap().amount
+    }
+
+    pub fn mint_supply(&self, key: &Pubkey) -> u64 {
+        let info = &self.accounts[key];
+        let data = info.data.borrow();
+        spl_token::state::Mint::unpack(&data).unwrap().supply
+    }
+
+    // Write program state directly, padded to `space` bytes
+    pub fn set_state<T: AccountSerialize>(&mut self, key: Pubkey, state: &T, space: usize) {
+        let mut data = Vec::with_capacity(space);
+        state.try_serialize(&mut data).unwrap();
+        assert!(data.len() <= space, "state does not fit in {space} bytes");
+        data.resize(space, 0);
+        let lamports = Rent::default().minimum_balance(space);
+        self.set_account(key, lamports, ofund_token::ID, &data, false);
+    }
+
+    pub fn state<T: AccountDeserialize>(&self, key: &Pubkey) -> T {
+        let info = &self.accounts[key];
+        let data = info.data.borrow();
+        T::try_deserialize(&mut &data[..]).unwrap()
+    }
+
+    // Whether `key` holds a live (rent-paying) account
+    pub fn exists(&self, key: &Pubkey) -> bool {
+        self.accounts
+            .get(key)
+            .map_or(false, |info| info.lamports() > 0)
+    }
+
+    pub fn lamports(&self, key: &Pubkey) -> u64 {
+        self.accounts.get(key).map_or(0, |info| info.lamports())
+    }
+
+    pub fn owner(&self, key: &Pubkey) -> Pubkey {
+        *self.accounts[key].owner
+    }
+
+    pub fn data(&self, key: &Pubkey) -> Vec<u8> {
+        self.accounts[key].data.borrow().to_vec()
+    }
+
+    pub fn process<A: ToAccountMetas, I: InstructionData>(
+        &mut self,
+        accounts: A,
+        ix: I,
+    ) -> std::result::Result<(), ProgramError> {
+        self.process_with_remaining(accounts, &[], ix)
+    }
+
+    // Run one instruction atomically: on error every account is rolled back,
+    // as the runtime does for a failed transaction
+    pub fn process_with_remaining<A: ToAccountMetas, I: InstructionData>(
+        &mut self,
+        accounts: A,
+        remaining: &[AccountMeta],
+        ix: I,
+    ) -> std::result::Result<(), ProgramError> {
+        let mut metas = accounts.to_account_metas(None);
+        metas.extend_from_slice(remaining);
+
+        let mut infos = Vec::with_capacity(metas.len());
+        for meta in &metas {
+            if !self.accounts.contains_key(&meta.pubkey) {
+                self.set_account(meta.pubkey, 0, system_program::ID, &[], false);
+            }
+            let mut info = self.accounts[&meta.pubkey].clone();
+            info.is_signer = meta.is_signer;
+            info.is_writable = meta.is_writable;
+            infos.push(info);
+        }
+        let infos: &'static [AccountInfo<'static>] = Box::leak(infos.into_boxed_slice());
+
+        let snapshots: HashMap<Pubkey, Snapshot> = self
+            .accounts
+            .iter()
+            .map(|(key, info)| {
+                (
+                    *key,
+                    Snapshot {
+                        lamports: info.lamports(),
+                        owner: *info.owner,
+                        data: info.data.borrow().to_vec(),
+                    },
+                )
+            })
+            .collect();
+
+        let result = ofund_token::entry(&ofund_token::ID, infos, &ix.data());
+
+        for (key, snapshot) in snapshots {
+            let info = &self.accounts[&key];
+            let (lamports, owner, data) = match result {
+                Ok(()) => (info.lamports(), *info.owner, info.data.borrow().to_vec()),
+                Err(_) => (snapshot.lamports, snapshot.owner, snapshot.data),
+            };
+            // Re-serialize every account, as the next transaction would see it
+            let executable = info.executable;
+            self.set_account(key, lamports, owner, &data, executable);
+        }
+        result
+    }
+}
+
+struct Stubs;
+
+impl SyscallStubs for Stubs {
+    fn sol_log(&self, _message: &str) {}
+
+    fn sol_log_data(&self, _fields: &[&[u8]]) {}
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        CLOCK.with(|clock| unsafe { *(var_addr as *mut Clock) = clock.borrow().clone() });
+        SUCCESS
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        SUCCESS
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let signers = signers_seeds
+            .iter()
+            .map(|seeds| Pubkey::create_program_address(seeds, &ofund_token::ID))
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|_| ProgramError::InvalidSeeds)?;
+
+        let mut accounts = Vec::with_capacity(instruction.accounts.len());
+        for meta in &instruction.accounts {
+            let info = account_infos
+                .iter()
+                .find(|info| *info.key == meta.pubkey)
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+            if meta.is_signer && !info.is_signer && !signers.contains(&meta.pubkey) {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            let mut info = info.clone();
+            info.is_signer = meta.is_signer;
+            info.is_writable = meta.is_writable;
+            accounts.push(info);
+        }
+
+        if instruction.program_id == spl_token::ID {
+            spl_token::processor::Processor::process(
+                &instruction.program_id,
+                &accounts,
+                &instruction.data,
+            )
+        } else if instruction.program_id == system_program::ID {
+            process_system_instruction(&accounts, &instruction.data)
+        } else {
+            Err(ProgramError::IncorrectProgramId)
+        }
+    }
+}
+
+// The subset of the system program the handlers use
+fn process_system_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let u64_at = |offset: usize| u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+    let pubkey_at = |offset: usize| Pubkey::try_from(&data[offset..offset + 32]).unwrap();
+    let tag = u32::from_le_bytes(data[..4].try_into().unwrap());
+    match tag {
+        // CreateAccount { lamports, space, owner }
+        0 => {
+            let to = &accounts[1];
+            if to.lamports() > 0 || !to.data_is_empty() || *to.owner != system_program::ID {
+                return Err(ProgramError::AccountAlreadyInitialized);
+            }
+            transfer_lamports(&accounts[0], to, u64_at(4))?;
+            to.realloc(u64_at(12) as usize, true)?;
+            to.assign(&pubkey_at(20));
+            Ok(())
+        }
+        // Assign { owner }
+        1 => {
+            let owner = pubkey_at(4);
+            if *accounts[0].owner != owner {
+                require_unowned(&accounts[0])?;
+                accounts[0].assign(&owner);
+            }
+            Ok(())
+        }
+        // Transfer { lamports }
+        2 => transfer_lamports(&accounts[0], &accounts[1], u64_at(4)),
+        // Allocate { space }
+        8 => {
+            require_unowned(&accounts[0])?;
+            if !accounts[0].data_is_empty() {
+                return Err(ProgramError::AccountAlreadyInitialized);
+            }
+            accounts[0].realloc(u64_at(4) as usize, true)
+        }
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+// Only the system program's own, signed accounts can be allocated or assigned
+fn require_unowned(account: &AccountInfo) -> ProgramResult {
+    if !account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if *account.owner != system_program::ID {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    Ok(())
+}
+
+fn transfer_lamports(from: &AccountInfo, to: &AccountInfo, lamports: u64) -> ProgramResult {
+    if !from.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    let remaining = from
+        .lamports()
+        .checked_sub(lamports)
+        .ok_or(ProgramError::InsufficientFunds)?;
+    **from.lamports.borrow_mut() = remaining;
+    **to.lamports.borrow_mut() += lamports;
+    Ok(())
+}
+
+// The error a handler returns for `error`, as seen by the runtime
+pub fn program_error(error: ofund_token::OtonomError) -> ProgramError {
+    anchor_lang::error::Error::from(error).into()
+}
+
+// The error an account constraint fails with, as seen by the runtime
+pub fn constraint_error(error: anchor_lang::error::ErrorCode) -> ProgramError {
+    anchor_lang::error::Error::from(error).into()
+}
+
+pub fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &ofund_token::ID).0
+}
+
+// An OFUND mint with its admin, stats, tier table, points config and
+// registration limiter set up the way the deployment scripts do
+pub struct Protocol {
+    pub env: Env,
+    pub admin: Pubkey,
+    pub mint: Pubkey,
+    pub mint_authority: Pubkey,
+    pub mint_authority_pda: Pubkey,
+}
+
+impl Protocol {
+    pub fn new() -> Self {
+        let mut env = Env::new();
+        env.set_time(1_700_000_000);
+        let admin = env.wallet();
+
+        // The mint is created off-chain, then handed to its mint-authority PDA.
+        // register_user checks both authority PDAs against the one stored bump,
+        // so use a mint whose two bumps agree.
+        let mint = loop {
+            let mint = env.create_mint(&Pubkey::default(), 9);
+            let bump = |seed: &[u8]| {
+                Pubkey::find_program_address(&[seed, mint.as_ref()], &ofund_token::ID).1
+            };
+            if bump(b"authority") == bump(b"mint-authority") {
+                break mint;
+            }
+        };
+        let mint_authority_pda = pda(&[b"mint-authority", mint.as_ref()]);
+        let mint_authority = pda(&[b"authority", mint.as_ref()]);
+        {
+            let info = &env.accounts[&mint];
+            let mut data = info.data.borrow_mut();
+            let mut state = spl_token::state::Mint::unpack(&data).unwrap();
+            state.mint_authority = COption::Some(mint_authority_pda);
+            state.pack_into_slice(&mut data);
+        }
+
+        let (_, authority_bump) =
+            Pubkey::find_program_address(&[b"authority", mint.as_ref()], &ofund_token::ID);
+        env.process(
+            ofund_token::accounts::InitializeExistingMint {
+                admin,
+                mint,
+                mint_authority_pda,
+                mint_authority,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+                rent: sysvar::rent::ID,
+            },
+            ofund_token::instruction::InitializeExistingMint {
+                authority_bump,
+                token_name: "OFUND".to_string(),
+                token_symbol: "OFUND".to_string(),
+                token_uri: String::new(),
+            },
+        )
+        .unwrap();
+
+        let mut protocol = Protocol {
+            env,
+            admin,
+            mint,
+            mint_authority,
+            mint_authority_pda,
+        };
+        protocol.initialize_admin_accounts();
+        protocol
+    }
+
+    fn initialize_admin_accounts(&mut self) {
+        let (admin, mint_authority) = (self.admin, self.mint_authority);
+        self.env.set_upgrade_authority(Some(admin));
+        let (program_data, program_config) = (self.env.program_data(), self.program_config());
+        self.env
+            .process(
+                ofund_token::accounts::InitializeProgramConfig {
+                    authority: admin,
+                    program: ofund_token::ID,
+                    program_data,
+                    mint_authority,
+                    program_config,
+                    system_program: system_program::ID,
+                },
+                ofund_token::instruction::InitializeProgramConfig {},
+            )
+            .unwrap();
+
+        let global_stats = self.global_stats();
+        self.env
+            .process(
+                ofund_token::accounts::InitializeGlobalStats {
+                    admin,
+                    mint_authority,
+                    admin_multisig: None,
+                    global_stats,
+                    system_program: system_program::ID,
+                },
+                ofund_token::instruction::InitializeGlobalStats {},
+            )
+            .unwrap();
+
+        let tier_config = self.tier_config();
+        self.env
+            .process(
+                ofund_token::accounts::InitializeTierConfig {
+                    admin,
+                    mint_authority,
+                    admin_multisig: None,
+                    tier_config,
+                    system_program: system_program::ID,
+                },
+                ofund_token::instruction::InitializeTierConfig {},
+            )
+            .unwrap();
+
+        let points_config = self.points_config();
+        self.env
+            .process(
+                ofund_token::accounts::InitializePointsConfig {
+                    admin,
+                    mint_authority,
+                    admin_multisig: None,
+                    points_config,
+                    system_program: system_program::ID,
+                },
+                ofund_token::instruction::InitializePointsConfig {
+                    rates: vec![ofund_token::PointsRate {
+                        mint: self.mint,
+                        points_per_token: 1,
+                    }],
+                    points_per_lottery_ticket: 1_000,
+                    max_bonus_tickets: 2,
+                },
+            )
+            .unwrap();
+
+        let registration_limiter = self.registration_limiter();
+        self.env
+            .process(
+                ofund_token::accounts::InitializeRegistrationLimiter {
+                    admin,
+                    mint_authority,
+                    admin_multisig: None,
+                    registration_limiter,
+                    system_program: system_program::ID,
+                },
+                ofund_token::instruction::InitializeRegistrationLimiter {
+                    window_duration: 86_400,
+                    max_registrations_per_window: 0,
+                    registration_fee: 0,
+                    treasury: admin,
+                },
+            )
+            .unwrap();
+    }
+
+    // Register `user`, returning the OFUND token account that received the grant
+    pub fn register(&mut self, user: Pubkey) -> Pubkey {
+        let user_token_account = self.env.create_token_account(&self.mint, &user, 0);
+        let (user_profile, bump) =
+            Pubkey::find_program_address(&[b"user-profile", user.as_ref()], &ofund_token::ID);
+        let accounts = ofund_token::accounts::RegisterUser {
+            user,
+            user_profile,
+            mint: self.mint,
+            registration: pda(&[b"registration", self.mint.as_ref(), user.as_ref()]),
+            user_token_account,
+            mint_authority: self.mint_authority,
+            mint_authority_pda: self.mint_authority_pda,
+            blocklist_entry: pda(&[b"blocklist", self.mint.as_ref(), user.as_ref()]),
+            registration_limiter: self.registration_limiter(),
+            treasury: self.admin,
+            global_stats: self.global_stats(),
+            referrer_profile: None,
+            referrer_token_account: None,
+            program_config: self.program_config(),
+            tier_config: self.tier_config(),
+            token_program: spl_token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        };
+        self.env
+            .process(accounts, ofund_token::instruction::RegisterUser { user_bump: bump })
+            .unwrap();
+        user_token_account
+    }
+
+    // Create a project under the OFUND mint, paid in `payment_mint`
+    pub fn create_project(&mut self, authority: Pubkey, payment_mint: Pubkey) -> ProjectKeys {
+        let (mint, global_stats) = (self.mint, self.global_stats());
+        self.create_project_under(mint, global_stats, authority, payment_mint)
+    }
+
+    // Create a project under any mint with initialized global stats
+    pub fn create_project_under(
+        &mut self,
+        mint: Pubkey,
+        global_stats: Pubkey,
+        authority: Pubkey,
+        payment_mint: Pubkey,
+    ) -> ProjectKeys {
+        let stats: ofund_token::GlobalStats = self.env.state(&global_stats);
+        let project = pda(&[b"project", mint.as_ref(), &stats.project_count.to_le_bytes()]);
+        let vault = self.env.create_token_account(&payment_mint, &project, 0);
+        self.env
+            .process(
+                ofund_token::accounts::InitializeProject {
+                    authority,
+                    mint,
+                    global_stats,
+                    project,
+                    project_vault: vault,
+                    payment_mint,
+                    system_program: system_program::ID,
+                    rent: sysvar::rent::ID,
+                },
+                ofund_token::instruction::InitializeProject {
+                    project_name: format!("Project {}", stats.project_count),
+                },
+            )
+            .unwrap();
+        ProjectKeys {
+            project,
+            vault,
+            mint,
+            payment_mint,
+            global_stats,
+            authority,
+        }
+    }
+
+    pub fn configure_sale(
+        &mut self,
+        project: &ProjectKeys,
+        sale_mode: ofund_token::SaleMode,
+        hard_cap: u64,
+        start_time: i64,
+        end_time: i64,
+    ) -> std::result::Result<(), ProgramError> {
+        self.env.process(
+            ofund_token::accounts::ConfigureSale {
+                authority: project.authority,
+                project: project.project,
+                project_vault: project.vault,
+            },
+            ofund_token::instruction::ConfigureSale {
+                sale_mode,
+                hard_cap,
+                start_time,
+                end_time,
+            },
+        )
+    }
+
+    // Create a standard OFUND project whose sale runs for `duration` seconds
+    pub fn open_project(&mut self, authority: Pubkey, duration: i64) -> ProjectKeys {
+        let project = self.create_project(authority, self.mint);
+        let end_time = self.env.now() + duration;
+        self.configure_sale(&project, ofund_token::SaleMode::Standard, 0, 0, end_time)
+            .unwrap();
+        project
+    }
+
+    // Accounts for `investor` investing in `project` with no lottery, KYC or snapshot
+    pub fn invest_accounts(
+        &self,
+        investor: Pubkey,
+        investor_token_account: Pubkey,
+        project: &ProjectKeys,
+    ) -> ofund_token::accounts::InvestInProject {
+        ofund_token::accounts::InvestInProject {
+            investor,
+            user_profile: self.user_profile(&investor),
+            project: project.project,
+            investor_token_account,
+            project_vault: project.vault,
+            payment_mint: project.payment_mint,
+            blocklist_entry: pda(&[b"blocklist", project.mint.as_ref(), investor.as_ref()]),
+            global_stats: project.global_stats,
+            contribution: project.contribution(&investor),
+            lottery: None,
+            lottery_ticket: None,
+            attestation: None,
+            kyc_config: None,
+            tier_snapshot: None,
+            program_config: self.program_config(),
+            tier_config: self.tier_config(),
+            points_config: self.points_config(),
+            token_program: spl_token::ID,
+            system_program: system_program::ID,
+        }
+    }
+
+    pub fn invest(
+        &mut self,
+        investor: Pubkey,
+        investor_token_account: Pubkey,
+        project: &ProjectKeys,
+        amount: u64,
+    ) -> std::result::Result<(), ProgramError> {
+        let accounts = self.invest_accounts(investor, investor_token_account, project);
+        self.env
+            .process(accounts, ofund_token::instruction::InvestInProject { amount })
+    }
+
+    pub fn withdraw(
+        &mut self,
+        investor: Pubkey,
+        investor_token_account: Pubkey,
+        project: &ProjectKeys,
+        amount: u64,
+    ) -> std::result::Result<(), ProgramError> {
+        let accounts = ofund_token::accounts::WithdrawInvestment {
+            investor,
+            project: project.project,
+            contribution: project.contribution(&investor),
+            user_profile: self.user_profile(&investor),
+            project_vault: project.vault,
+            investor_token_account,
+            treasury_token_account: None,
+            payment_mint: project.payment_mint,
+            lottery_ticket: None,
+            global_stats: project.global_stats,
+            program_config: self.program_config(),
+            tier_config: self.tier_config(),
+            token_program: spl_token::ID,
+        };
+        self.env
+            .process(accounts, ofund_token::instruction::WithdrawInvestment { amount })
+    }
+
+    pub fn program_config(&self) -> Pubkey {
+        pda(&[b"program-config"])
+    }
+
+    pub fn global_stats(&self) -> Pubkey {
+        pda(&[b"global-stats", self.mint.as_ref()])
+    }
+
+    pub fn tier_config(&self) -> Pubkey {
+        pda(&[b"tier-config", self.mint.as_ref()])
+    }
+
+    pub fn points_config(&self) -> Pubkey {
+        pda(&[b"points-config", self.mint.as_ref()])
+    }
+
+    pub fn registration_limiter(&self) -> Pubkey {
+        pda(&[b"registration-limiter", self.mint.as_ref()])
+    }
+
+    pub fn user_profile(&self, user: &Pubkey) -> Pubkey {
+        pda(&[b"user-profile", user.as_ref()])
+    }
+}
+
+pub struct ProjectKeys {
+    pub project: Pubkey,
+    pub vault: Pubkey,
+    pub mint: Pubkey,
+    pub payment_mint: Pubkey,
+    pub global_stats: Pubkey,
+    pub authority: Pubkey,
+}
+
+impl ProjectKeys {
+    pub fn contribution(&self, user: &Pubkey) -> Pubkey {
+        pda(&[b"contribution", self.project.as_ref(), user.as_ref()])
+    }
+}
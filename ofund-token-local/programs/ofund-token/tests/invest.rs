@@ -0,0 +1,163 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{system_program, sysvar};
+use anchor_spl::token::spl_token;
+use common::{pda, program_error, Protocol};
+use ofund_token::{
+    accounts, instruction, GlobalStats, OtonomError, SaleMode, TierBenefit, UserProfile,
+};
+
+const OFUND: u64 = 1_000_000_000;
+
+// Give `mint` its own mint authority and global stats, as anyone
+// can for a mint they created, returning its global stats account
+fn adopt_mint(protocol: &mut Protocol, admin: Pubkey, mint: Pubkey) -> Pubkey {
+    let (mint_authority, authority_bump) =
+        Pubkey::find_program_address(&[b"authority", mint.as_ref()], &ofund_token::ID);
+    protocol
+        .env
+        .process(
+            accounts::InitializeExistingMint {
+                admin,
+                mint,
+                mint_authority_pda: pda(&[b"mint-authority", mint.as_ref()]),
+                mint_authority,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+                rent: sysvar::rent::ID,
+            },
+            instruction::InitializeExistingMint {
+                authority_bump,
+                token_name: "Junk".to_string(),
+                token_symbol: "JUNK".to_string(),
+                token_uri: String::new(),
+            },
+        )
+        .unwrap();
+
+    let global_stats = pda(&[b"global-stats", mint.as_ref()]);
+    protocol
+        .env
+        .process(
+            accounts::InitializeGlobalStats {
+                admin,
+                mint_authority,
+                admin_multisig: None,
+                global_stats,
+                system_program: system_program::ID,
+            },
+            instruction::InitializeGlobalStats {},
+        )
+        .unwrap();
+    global_stats
+}
+
+#[test]
+fn only_ofund_investments_count_towards_the_tier() {
+    let mut protocol = Protocol::new();
+    let alice = protocol.env.wallet();
+    let owner = protocol.env.wallet();
+    let alice_ofund = protocol.register(alice);
+
+    // A project under a self-made mint that is also its payment mint
+    let junk = protocol.env.create_mint(&alice, 9);
+    let junk_stats = adopt_mint(&mut protocol, alice, junk);
+    let junk_project = protocol.create_project_under(junk, junk_stats, owner, junk);
+    let end_time = protocol.env.now() + 1_000;
+    protocol
+        .configure_sale(&junk_project, SaleMode::Standard, 0, 0, end_time)
+        .unwrap();
+    let alice_junk = protocol.env.create_token_account(&junk, &alice, 5_000 * OFUND);
+    protocol
+        .invest(alice, alice_junk, &junk_project, 5_000 * OFUND)
+        .unwrap();
+
+    let profile: UserProfile = protocol.env.state(&protocol.user_profile(&alice));
+    assert_eq!(profile.total_invested, 0);
+    assert_eq!(profile.tier, 0);
+    assert_eq!(profile.currency_totals.len(), 1);
+    assert_eq!(profile.currency_totals[0].mint, junk);
+    assert_eq!(profile.currency_totals[0].amount, 5_000 * OFUND);
+    let stats: GlobalStats = protocol.env.state(&junk_stats);
+    assert_eq!(stats.total_ofund_raised, 0);
+
+    let project = protocol.open_project(owner, 1_000);
+    protocol
+        .invest(alice, alice_ofund, &project, 1_000 * OFUND)
+        .unwrap();
+
+    let profile: UserProfile = protocol.env.state(&protocol.user_profile(&alice));
+    assert_eq!(profile.total_invested, 1_000 * OFUND);
+    assert_eq!(profile.tier, 1);
+    let stats: GlobalStats = protocol.env.state(&protocol.global_stats());
+    assert_eq!(stats.total_ofund_raised, 1_000 * OFUND);
+    assert_eq!(protocol.env.token_balance(&project.vault), 1_000 * OFUND);
+}
+
+#[test]
+fn ticket_size_caps_each_investment_and_tier_allocation_caps_the_total() {
+    let mut protocol = Protocol::new();
+    let owner = protocol.env.wallet();
+    let alice = protocol.env.wallet();
+    let carol = protocol.env.wallet();
+    let alice_ofund = protocol.register(alice);
+    let carol_ofund = protocol.register(carol);
+
+    // Carol reaches tier 1 in an earlier raise
+    let earlier = protocol.open_project(owner, 1_000);
+    protocol.invest(carol, carol_ofund, &earlier, 1_000 * OFUND).unwrap();
+
+    let project = protocol.open_project(owner, 1_000);
+    protocol
+        .env
+        .process(
+            accounts::ConfigureTicketSize {
+                authority: owner,
+                project: project.project,
+            },
+            instruction::ConfigureTicketSize {
+                min_investment: 0,
+                max_investment: 500 * OFUND,
+            },
+        )
+        .unwrap();
+    let benefit = |weight_bps| TierBenefit {
+        weight_bps,
+        access_delay: 0,
+    };
+    protocol
+        .env
+        .process(
+            accounts::ConfigureTierBenefits {
+                authority: owner,
+                project: project.project,
+            },
+            instruction::ConfigureTierBenefits {
+                benefits: vec![benefit(10_000), benefit(20_000)],
+                max_allocation: 800 * OFUND,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        protocol.invest(alice, alice_ofund, &project, 501 * OFUND),
+        Err(program_error(OtonomError::InvestmentAboveMaximum))
+    );
+    protocol.invest(alice, alice_ofund, &project, 500 * OFUND).unwrap();
+    protocol.invest(alice, alice_ofund, &project, 300 * OFUND).unwrap();
+    assert_eq!(
+        protocol.invest(alice, alice_ofund, &project, OFUND),
+        Err(program_error(OtonomError::AllocationExceeded))
+    );
+
+    // Tier 1 doubles the allocation, but not the ticket size
+    for _ in 0..3 {
+        protocol.invest(carol, carol_ofund, &project, 500 * OFUND).unwrap();
+    }
+    protocol.invest(carol, carol_ofund, &project, 100 * OFUND).unwrap();
+    assert_eq!(
+        protocol.invest(carol, carol_ofund, &project, OFUND),
+        Err(program_error(OtonomError::AllocationExceeded))
+    );
+}
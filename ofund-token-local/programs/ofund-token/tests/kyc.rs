@@ -0,0 +1,154 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use common::{pda, program_error, ProjectKeys, Protocol};
+use ofund_token::{accounts, instruction, AdminAction, OtonomError, SaleMode};
+
+const OFUND: u64 = 1_000_000_000;
+const DAY: i64 = 24 * 60 * 60;
+
+fn kyc_config(protocol: &Protocol) -> Pubkey {
+    pda(&[b"kyc-config", protocol.mint.as_ref()])
+}
+
+fn attestation(protocol: &Protocol, user: &Pubkey) -> Pubkey {
+    pda(&[b"attestation", protocol.mint.as_ref(), user.as_ref()])
+}
+
+fn issue_attestation(protocol: &mut Protocol, attester: Pubkey, user: Pubkey) {
+    let expires_at = protocol.env.now() + 30 * DAY;
+    let accounts = accounts::IssueAttestation {
+        attester,
+        kyc_config: kyc_config(protocol),
+        attestation: attestation(protocol, &user),
+        system_program: system_program::ID,
+    };
+    protocol
+        .env
+        .process(accounts, instruction::IssueAttestation { user, expires_at })
+        .unwrap();
+}
+
+fn set_kyc_requirement(
+    protocol: &mut Protocol,
+    project: &ProjectKeys,
+    requires_kyc: bool,
+) -> std::result::Result<(), ProgramError> {
+    protocol.env.process(
+        accounts::SetKycRequirement {
+            authority: project.authority,
+            project: project.project,
+        },
+        instruction::SetKycRequirement { requires_kyc },
+    )
+}
+
+// Replace the attester through the admin timelock
+fn rotate_attester(protocol: &mut Protocol, attester: Pubkey) {
+    let (admin, mint_authority) = (protocol.admin, protocol.mint_authority);
+    let pending_action = pda(&[b"pending-action", protocol.mint.as_ref(), &1u64.to_le_bytes()]);
+    let eta = protocol.env.now() + 2 * DAY;
+    protocol
+        .env
+        .process(
+            accounts::QueueAdminAction {
+                proposer: admin,
+                mint_authority,
+                admin_multisig: None,
+                pending_action,
+                system_program: system_program::ID,
+            },
+            instruction::QueueAdminAction {
+                action_id: 1,
+                action: AdminAction::SetAttester { attester },
+                eta,
+            },
+        )
+        .unwrap();
+    protocol.env.set_time(eta);
+    let kyc_config = kyc_config(protocol);
+    protocol
+        .env
+        .process(
+            accounts::ExecuteAdminAction {
+                mint_authority,
+                pending_action,
+                proposer: admin,
+                admin_multisig: None,
+                roles: None,
+                kyc_config: Some(kyc_config),
+                registration_limiter: None,
+                tier_config: None,
+                points_config: None,
+            },
+            instruction::ExecuteAdminAction {},
+        )
+        .unwrap();
+}
+
+#[test]
+fn rotating_the_attester_retires_its_attestations() {
+    let mut protocol = Protocol::new();
+    let owner = protocol.env.wallet();
+    let alice = protocol.env.wallet();
+    let old_attester = protocol.env.wallet();
+    let new_attester = protocol.env.wallet();
+    let alice_ofund = protocol.register(alice);
+
+    let (admin, mint_authority) = (protocol.admin, protocol.mint_authority);
+    let accounts = accounts::InitializeKycConfig {
+        admin,
+        mint_authority,
+        admin_multisig: None,
+        kyc_config: kyc_config(&protocol),
+        system_program: system_program::ID,
+    };
+    protocol
+        .env
+        .process(
+            accounts,
+            instruction::InitializeKycConfig {
+                attester: old_attester,
+            },
+        )
+        .unwrap();
+
+    let ofund = protocol.mint;
+    let project = protocol.create_project(owner, ofund);
+    set_kyc_requirement(&mut protocol, &project, true).unwrap();
+    let end_time = protocol.env.now() + 10 * DAY;
+    protocol
+        .configure_sale(&project, SaleMode::Standard, 0, 0, end_time)
+        .unwrap();
+    issue_attestation(&mut protocol, old_attester, alice);
+
+    let invest = |protocol: &mut Protocol, with_kyc_config: bool| {
+        let mut accounts = protocol.invest_accounts(alice, alice_ofund, &project);
+        accounts.attestation = Some(attestation(protocol, &alice));
+        accounts.kyc_config = with_kyc_config.then(|| kyc_config(protocol));
+        let amount = 10 * OFUND;
+        protocol
+            .env
+            .process(accounts, instruction::InvestInProject { amount })
+    };
+    assert_eq!(
+        invest(&mut protocol, false),
+        Err(program_error(OtonomError::KycConfigRequired))
+    );
+
+    rotate_attester(&mut protocol, new_attester);
+    assert_eq!(
+        invest(&mut protocol, true),
+        Err(program_error(OtonomError::AttesterMismatch))
+    );
+
+    issue_attestation(&mut protocol, new_attester, alice);
+    invest(&mut protocol, true).unwrap();
+
+    // The requirement is fixed once the raise has opened
+    assert_eq!(
+        set_kyc_requirement(&mut protocol, &project, false),
+        Err(program_error(OtonomError::SaleAlreadyStarted))
+    );
+}
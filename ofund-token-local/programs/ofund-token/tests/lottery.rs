@@ -0,0 +1,146 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{system_program, sysvar};
+use common::{pda, program_error, ProjectKeys, Protocol};
+use ofund_token::{accounts, instruction, Lottery, LotteryTicket, OtonomError, TierConfig};
+
+const OFUND: u64 = 1_000_000_000;
+
+fn lottery(project: &ProjectKeys) -> Pubkey {
+    pda(&[b"lottery", project.project.as_ref()])
+}
+
+fn lottery_ticket(project: &ProjectKeys, user: &Pubkey) -> Pubkey {
+    pda(&[b"lottery-ticket", project.project.as_ref(), user.as_ref()])
+}
+
+fn register(protocol: &mut Protocol, project: &ProjectKeys, user: Pubkey) -> LotteryTicket {
+    let accounts = accounts::RegisterLotteryTickets {
+        user,
+        user_profile: protocol.user_profile(&user),
+        project: project.project,
+        lottery: lottery(project),
+        tier_snapshot: None,
+        lottery_ticket: lottery_ticket(project, &user),
+        program_config: protocol.program_config(),
+        tier_config: protocol.tier_config(),
+        points_config: protocol.points_config(),
+        system_program: system_program::ID,
+    };
+    protocol
+        .env
+        .process(accounts, instruction::RegisterLotteryTickets {})
+        .unwrap();
+    protocol.env.state(&lottery_ticket(project, &user))
+}
+
+// A project whose lottery closes registration in 100 seconds, with every ticket winning
+fn lottery_project(protocol: &mut Protocol, owner: Pubkey) -> ProjectKeys {
+    let project = protocol.open_project(owner, 10_000);
+    let now = protocol.env.now();
+    protocol
+        .env
+        .process(
+            accounts::InitializeLottery {
+                authority: owner,
+                project: project.project,
+                lottery: lottery(&project),
+                system_program: system_program::ID,
+            },
+            instruction::InitializeLottery {
+                registration_end: now + 100,
+                investment_end: now + 1_000,
+                winning_tickets: 1_000,
+                allocation_per_ticket: 50 * OFUND,
+            },
+        )
+        .unwrap();
+    project
+}
+
+fn draw(protocol: &mut Protocol, project: &ProjectKeys) {
+    let lottery = lottery(project);
+    protocol.env.set_time(protocol.env.now() + 100);
+    protocol
+        .env
+        .process(
+            accounts::CommitLotteryDraw { lottery },
+            instruction::CommitLotteryDraw {},
+        )
+        .unwrap();
+    let state: Lottery = protocol.env.state(&lottery);
+    protocol.env.set_time(state.commit_slot as i64 + 1);
+
+    // SlotHashes holding only the committed slot
+    let mut slot_hashes = 1u64.to_le_bytes().to_vec();
+    slot_hashes.extend_from_slice(&state.commit_slot.to_le_bytes());
+    slot_hashes.extend_from_slice(&[7; 32]);
+    protocol
+        .env
+        .set_account(sysvar::slot_hashes::ID, 1, sysvar::ID, &slot_hashes, false);
+    protocol
+        .env
+        .process(
+            accounts::DrawLottery {
+                lottery,
+                slot_hashes: sysvar::slot_hashes::ID,
+            },
+            instruction::DrawLottery {},
+        )
+        .unwrap();
+}
+
+#[test]
+fn winning_tickets_are_counted_once() {
+    let mut protocol = Protocol::new();
+    let owner = protocol.env.wallet();
+    let alice = protocol.env.wallet();
+    let alice_ofund = protocol.register(alice);
+    let project = lottery_project(&mut protocol, owner);
+
+    let ticket = register(&mut protocol, &project, alice);
+    assert_eq!(ticket.ticket_count, 1);
+    assert_eq!(ticket.wins, None);
+    draw(&mut protocol, &project);
+
+    let invest = |protocol: &mut Protocol, amount| {
+        let mut accounts = protocol.invest_accounts(alice, alice_ofund, &project);
+        accounts.lottery = Some(lottery(&project));
+        accounts.lottery_ticket = Some(lottery_ticket(&project, &alice));
+        protocol
+            .env
+            .process(accounts, instruction::InvestInProject { amount })
+    };
+    invest(&mut protocol, 20 * OFUND).unwrap();
+    let ticket: LotteryTicket = protocol.env.state(&lottery_ticket(&project, &alice));
+    assert_eq!(ticket.wins, Some(1));
+
+    // Later investments use the stored count rather than recounting
+    let mut state: Lottery = protocol.env.state(&lottery(&project));
+    state.winning_tickets = 0;
+    protocol.env.set_state(lottery(&project), &state, 200);
+    invest(&mut protocol, 30 * OFUND).unwrap();
+    assert_eq!(
+        invest(&mut protocol, OFUND),
+        Err(program_error(OtonomError::LotteryAllocationExceeded))
+    );
+}
+
+#[test]
+fn lottery_tickets_per_user_are_capped() {
+    let mut protocol = Protocol::new();
+    let owner = protocol.env.wallet();
+    let alice = protocol.env.wallet();
+    protocol.register(alice);
+
+    // A tier table written before levels were bounded
+    let tier_config = protocol.tier_config();
+    let mut table: TierConfig = protocol.env.state(&tier_config);
+    table.tiers[0].lottery_tickets = 1_000;
+    protocol.env.set_state(tier_config, &table, 2_000);
+
+    let project = lottery_project(&mut protocol, owner);
+    let ticket = register(&mut protocol, &project, alice);
+    assert_eq!(ticket.ticket_count, 64);
+}
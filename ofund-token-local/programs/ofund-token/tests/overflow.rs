@@ -0,0 +1,103 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token;
+use common::{program_error, ProjectKeys, Protocol};
+use ofund_token::{accounts, instruction, OtonomError, Project, SaleMode};
+
+const OFUND: u64 = 1_000_000_000;
+
+fn settle_overflow(
+    protocol: &mut Protocol,
+    project: &ProjectKeys,
+    investor: Pubkey,
+    investor_token_account: Pubkey,
+) -> std::result::Result<(), ProgramError> {
+    let accounts = accounts::SettleOverflow {
+        project: project.project,
+        contribution: project.contribution(&investor),
+        user_profile: protocol.user_profile(&investor),
+        project_vault: project.vault,
+        investor_token_account,
+        payment_mint: project.payment_mint,
+        global_stats: project.global_stats,
+        program_config: protocol.program_config(),
+        tier_config: protocol.tier_config(),
+        token_program: spl_token::ID,
+    };
+    protocol.env.process(accounts, instruction::SettleOverflow {})
+}
+
+fn settle(protocol: &mut Protocol, project: &ProjectKeys) -> std::result::Result<(), ProgramError> {
+    protocol.env.process(
+        accounts::SettleProject {
+            authority: project.authority,
+            project: project.project,
+        },
+        instruction::SettleProject {},
+    )
+}
+
+fn unsettled(protocol: &Protocol, project: &ProjectKeys) -> u32 {
+    let state: Project = protocol.env.state(&project.project);
+    state.unsettled_contributions
+}
+
+#[test]
+fn sale_config_locks_at_the_first_deposit() {
+    let mut protocol = Protocol::new();
+    let owner = protocol.env.wallet();
+    let alice = protocol.env.wallet();
+    let alice_ofund = protocol.register(alice);
+    let project = protocol.open_project(owner, 1_000);
+
+    protocol.invest(alice, alice_ofund, &project, 5 * OFUND).unwrap();
+    protocol.withdraw(alice, alice_ofund, &project, 5 * OFUND).unwrap();
+    let state: Project = protocol.env.state(&project.project);
+    assert_eq!(state.total_raised, 0);
+
+    // Switching an emptied standard sale to overflow would skip its deposits
+    let end_time = protocol.env.now() + 1_000;
+    assert_eq!(
+        protocol.configure_sale(&project, SaleMode::Overflow, 10 * OFUND, 0, end_time),
+        Err(program_error(OtonomError::SaleAlreadyStarted))
+    );
+}
+
+#[test]
+fn overflow_contributions_topped_up_after_withdrawal_must_settle() {
+    let mut protocol = Protocol::new();
+    let owner = protocol.env.wallet();
+    let alice = protocol.env.wallet();
+    let bob = protocol.env.wallet();
+    let alice_ofund = protocol.register(alice);
+    let bob_ofund = protocol.register(bob);
+    let ofund = protocol.mint;
+    let project = protocol.create_project(owner, ofund);
+    let end_time = protocol.env.now() + 1_000;
+    protocol
+        .configure_sale(&project, SaleMode::Overflow, 10 * OFUND, 0, end_time)
+        .unwrap();
+
+    protocol.invest(alice, alice_ofund, &project, 5 * OFUND).unwrap();
+    assert_eq!(unsettled(&protocol, &project), 1);
+    protocol.withdraw(alice, alice_ofund, &project, 5 * OFUND).unwrap();
+    assert_eq!(unsettled(&protocol, &project), 0);
+    protocol.invest(alice, alice_ofund, &project, 10 * OFUND).unwrap();
+    protocol.invest(bob, bob_ofund, &project, 10 * OFUND).unwrap();
+    assert_eq!(unsettled(&protocol, &project), 2);
+
+    protocol.env.set_time(end_time);
+    settle_overflow(&mut protocol, &project, bob, bob_ofund).unwrap();
+    assert_eq!(
+        settle(&mut protocol, &project),
+        Err(program_error(OtonomError::OverflowNotSettled))
+    );
+
+    // Half of each deposit is accepted against the 10 OFUND cap
+    settle_overflow(&mut protocol, &project, alice, alice_ofund).unwrap();
+    assert_eq!(unsettled(&protocol, &project), 0);
+    settle(&mut protocol, &project).unwrap();
+    assert_eq!(protocol.env.token_balance(&project.vault), 10 * OFUND);
+    assert_eq!(protocol.env.token_balance(&alice_ofund), 100_000 * OFUND - 5 * OFUND);
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use common::{program_error, ProjectKeys, Protocol};
+use ofund_token::{accounts, instruction, Contribution, OtonomError, UserProfile};
+
+const OFUND: u64 = 1_000_000_000;
+
+fn claim_loyalty_points(
+    protocol: &mut Protocol,
+    project: &ProjectKeys,
+    user: &Pubkey,
+) -> std::result::Result<(), ProgramError> {
+    let accounts = accounts::ClaimLoyaltyPoints {
+        project: project.project,
+        contribution: project.contribution(user),
+        user_profile: protocol.user_profile(user),
+    };
+    protocol
+        .env
+        .process(accounts, instruction::ClaimLoyaltyPoints {})
+}
+
+fn pending_points(protocol: &Protocol, project: &ProjectKeys, user: &Pubkey) -> u64 {
+    let contribution: Contribution = protocol.env.state(&project.contribution(user));
+    contribution.points_earned
+}
+
+fn loyalty_points(protocol: &Protocol, user: &Pubkey) -> u64 {
+    let profile: UserProfile = protocol.env.state(&protocol.user_profile(user));
+    profile.loyalty_points
+}
+
+#[test]
+fn loyalty_points_vest_when_the_project_settles() {
+    let mut protocol = Protocol::new();
+    let owner = protocol.env.wallet();
+    let alice = protocol.env.wallet();
+    let alice_ofund = protocol.register(alice);
+    let project = protocol.open_project(owner, 1_000);
+
+    protocol.invest(alice, alice_ofund, &project, 3_000 * OFUND).unwrap();
+    assert_eq!(pending_points(&protocol, &project, &alice), 3_000);
+    assert_eq!(loyalty_points(&protocol, &alice), 0);
+
+    // Withdrawn deposits take their pending points with them
+    protocol.withdraw(alice, alice_ofund, &project, 1_000 * OFUND).unwrap();
+    assert_eq!(pending_points(&protocol, &project, &alice), 2_000);
+    assert_eq!(
+        claim_loyalty_points(&mut protocol, &project, &alice),
+        Err(program_error(OtonomError::ProjectNotSettled))
+    );
+
+    let end_time = protocol.env.now() + 1_000;
+    protocol.env.set_time(end_time);
+    protocol
+        .env
+        .process(
+            accounts::SettleProject {
+                authority: project.authority,
+                project: project.project,
+            },
+            instruction::SettleProject {},
+        )
+        .unwrap();
+
+    claim_loyalty_points(&mut protocol, &project, &alice).unwrap();
+    assert_eq!(pending_points(&protocol, &project, &alice), 0);
+    assert_eq!(loyalty_points(&protocol, &alice), 2_000);
+
+    // Claiming again credits nothing more
+    claim_loyalty_points(&mut protocol, &project, &alice).unwrap();
+    assert_eq!(loyalty_points(&protocol, &alice), 2_000);
+}
+
+#[test]
+fn payment_mints_without_a_rate_earn_no_points() {
+    let mut protocol = Protocol::new();
+    let owner = protocol.env.wallet();
+    let alice = protocol.env.wallet();
+    protocol.register(alice);
+
+    let usdc = protocol.env.create_mint(&owner, 6);
+    let project = protocol.create_project(owner, usdc);
+    let end_time = protocol.env.now() + 1_000;
+    protocol
+        .configure_sale(&project, ofund_token::SaleMode::Standard, 0, 0, end_time)
+        .unwrap();
+    let alice_usdc = protocol.env.create_token_account(&usdc, &alice, 5_000_000_000);
+    protocol.invest(alice, alice_usdc, &project, 5_000_000_000).unwrap();
+
+    assert_eq!(pending_points(&protocol, &project, &alice), 0);
+}
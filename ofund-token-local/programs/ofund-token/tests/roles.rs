@@ -0,0 +1,112 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use common::{pda, program_error, ProjectKeys, Protocol};
+use ofund_token::{accounts, instruction, AdminAction, OtonomError};
+
+const OFUND: u64 = 1_000_000_000;
+const ROLE_PAUSER: u8 = 1 << 1;
+
+// Create the roles account and grant `role` to `member` through the admin timelock
+fn grant_role(protocol: &mut Protocol, member: Pubkey, role: u8) {
+    let (admin, mint_authority) = (protocol.admin, protocol.mint_authority);
+    let roles = pda(&[b"roles", protocol.mint.as_ref()]);
+    protocol
+        .env
+        .process(
+            accounts::InitializeRoles {
+                admin,
+                mint_authority,
+                admin_multisig: None,
+                roles,
+                system_program: system_program::ID,
+            },
+            instruction::InitializeRoles {},
+        )
+        .unwrap();
+
+    let pending_action = pda(&[b"pending-action", protocol.mint.as_ref(), &1u64.to_le_bytes()]);
+    let eta = protocol.env.now() + 2 * 24 * 60 * 60;
+    protocol
+        .env
+        .process(
+            accounts::QueueAdminAction {
+                proposer: admin,
+                mint_authority,
+                admin_multisig: None,
+                pending_action,
+                system_program: system_program::ID,
+            },
+            instruction::QueueAdminAction {
+                action_id: 1,
+                action: AdminAction::GrantRole { member, role },
+                eta,
+            },
+        )
+        .unwrap();
+    protocol.env.set_time(eta);
+    protocol
+        .env
+        .process(
+            accounts::ExecuteAdminAction {
+                mint_authority,
+                pending_action,
+                proposer: admin,
+                admin_multisig: None,
+                roles: Some(roles),
+                kyc_config: None,
+                registration_limiter: None,
+                tier_config: None,
+                points_config: None,
+            },
+            instruction::ExecuteAdminAction {},
+        )
+        .unwrap();
+}
+
+fn set_paused(
+    protocol: &mut Protocol,
+    project: &ProjectKeys,
+    authority: Pubkey,
+    paused: bool,
+) -> std::result::Result<(), ProgramError> {
+    let accounts = accounts::SetProjectPaused {
+        authority,
+        project: project.project,
+        mint_authority: protocol.mint_authority,
+        roles: Some(pda(&[b"roles", protocol.mint.as_ref()])),
+    };
+    protocol
+        .env
+        .process(accounts, instruction::SetProjectPaused { paused })
+}
+
+#[test]
+fn pausers_can_halt_deposits_but_not_withdrawals() {
+    let mut protocol = Protocol::new();
+    let owner = protocol.env.wallet();
+    let ops = protocol.env.wallet();
+    let alice = protocol.env.wallet();
+    let alice_ofund = protocol.register(alice);
+    grant_role(&mut protocol, ops, ROLE_PAUSER);
+
+    let project = protocol.open_project(owner, 1_000);
+    protocol.invest(alice, alice_ofund, &project, 100 * OFUND).unwrap();
+
+    // Owning the project is not enough
+    assert_eq!(
+        set_paused(&mut protocol, &project, owner, true),
+        Err(program_error(OtonomError::Unauthorized))
+    );
+    set_paused(&mut protocol, &project, ops, true).unwrap();
+
+    assert_eq!(
+        protocol.invest(alice, alice_ofund, &project, 10 * OFUND),
+        Err(program_error(OtonomError::ProjectPaused))
+    );
+    protocol.withdraw(alice, alice_ofund, &project, 40 * OFUND).unwrap();
+
+    set_paused(&mut protocol, &project, ops, false).unwrap();
+    protocol.invest(alice, alice_ofund, &project, 10 * OFUND).unwrap();
+}
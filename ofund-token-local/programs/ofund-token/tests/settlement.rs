@@ -0,0 +1,168 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+use anchor_spl::token::spl_token;
+use common::{pda, program_error, ProjectKeys, Protocol};
+use ofund_token::{
+    accounts, instruction, Contribution, OtonomError, Project, ProjectStatus, SaleMode,
+};
+
+const OFUND: u64 = 1_000_000_000;
+
+fn settle(protocol: &mut Protocol, project: &ProjectKeys) -> std::result::Result<(), ProgramError> {
+    protocol.env.process(
+        accounts::SettleProject {
+            authority: project.authority,
+            project: project.project,
+        },
+        instruction::SettleProject {},
+    )
+}
+
+fn cancel(protocol: &mut Protocol, project: &ProjectKeys) -> std::result::Result<(), ProgramError> {
+    let mint_authority = protocol.mint_authority;
+    protocol.env.process(
+        accounts::CancelProject {
+            authority: project.authority,
+            project: project.project,
+            project_vault: project.vault,
+            mint_authority,
+            roles: None,
+        },
+        instruction::CancelProject {},
+    )
+}
+
+fn claim_refund(
+    protocol: &mut Protocol,
+    project: &ProjectKeys,
+    investor: Pubkey,
+    investor_token_account: Pubkey,
+) -> std::result::Result<(), ProgramError> {
+    let accounts = accounts::ClaimRefund {
+        investor,
+        project: project.project,
+        contribution: project.contribution(&investor),
+        user_profile: protocol.user_profile(&investor),
+        project_vault: project.vault,
+        investor_token_account,
+        payment_mint: project.payment_mint,
+        global_stats: project.global_stats,
+        program_config: protocol.program_config(),
+        tier_config: protocol.tier_config(),
+        token_program: spl_token::ID,
+    };
+    protocol.env.process(accounts, instruction::ClaimRefund {})
+}
+
+fn close_project(
+    protocol: &mut Protocol,
+    project: &ProjectKeys,
+) -> std::result::Result<(), ProgramError> {
+    let state: Project = protocol.env.state(&project.project);
+    let name_hash = keccak::hash(state.name.to_lowercase().as_bytes()).0;
+    protocol.env.process(
+        accounts::CloseProject {
+            authority: project.authority,
+            project: project.project,
+            project_vault: project.vault,
+            project_name: pda(&[b"project-name", project.mint.as_ref(), &name_hash]),
+            token_program: spl_token::ID,
+        },
+        instruction::CloseProject {},
+    )
+}
+
+#[test]
+fn deposits_wait_for_a_sale_end_time() {
+    let mut protocol = Protocol::new();
+    let owner = protocol.env.wallet();
+    let alice = protocol.env.wallet();
+    let alice_ofund = protocol.register(alice);
+    let ofund = protocol.mint;
+    let project = protocol.create_project(owner, ofund);
+
+    assert_eq!(
+        protocol.invest(alice, alice_ofund, &project, 10 * OFUND),
+        Err(program_error(OtonomError::SaleNotConfigured))
+    );
+    assert_eq!(
+        protocol.configure_sale(&project, SaleMode::Standard, 0, 0, 0),
+        Err(program_error(OtonomError::InvalidSaleConfig))
+    );
+
+    let end_time = protocol.env.now() + 1_000;
+    protocol
+        .configure_sale(&project, SaleMode::Standard, 0, 0, end_time)
+        .unwrap();
+    protocol.invest(alice, alice_ofund, &project, 10 * OFUND).unwrap();
+
+    // Without a filled hard cap the sale settles once its window closes
+    assert_eq!(
+        settle(&mut protocol, &project),
+        Err(program_error(OtonomError::SaleNotEnded))
+    );
+    protocol.env.set_time(end_time);
+    settle(&mut protocol, &project).unwrap();
+    let state: Project = protocol.env.state(&project.project);
+    assert!(state.status == ProjectStatus::Settled);
+}
+
+#[test]
+fn cancelled_project_refunds_every_investor_before_closing() {
+    let mut protocol = Protocol::new();
+    let owner = protocol.env.wallet();
+    let alice = protocol.env.wallet();
+    let bob = protocol.env.wallet();
+    let alice_ofund = protocol.register(alice);
+    let bob_ofund = protocol.register(bob);
+    let project = protocol.open_project(owner, 1_000);
+
+    protocol.invest(alice, alice_ofund, &project, 10 * OFUND).unwrap();
+    protocol.invest(alice, alice_ofund, &project, 5 * OFUND).unwrap();
+    protocol.invest(bob, bob_ofund, &project, 20 * OFUND).unwrap();
+    let state: Project = protocol.env.state(&project.project);
+    assert_eq!(state.open_contributions, 2);
+    assert_eq!(state.total_raised, 35 * OFUND);
+
+    assert_eq!(
+        claim_refund(&mut protocol, &project, alice, alice_ofund),
+        Err(program_error(OtonomError::ProjectNotCancelled))
+    );
+    cancel(&mut protocol, &project).unwrap();
+    assert_eq!(
+        protocol.invest(bob, bob_ofund, &project, OFUND),
+        Err(program_error(OtonomError::ProjectNotActive))
+    );
+
+    let grant = 100_000 * OFUND;
+    claim_refund(&mut protocol, &project, alice, alice_ofund).unwrap();
+    assert_eq!(protocol.env.token_balance(&alice_ofund), grant);
+    let contribution: Contribution = protocol.env.state(&project.contribution(&alice));
+    assert!(contribution.refunded);
+    assert_eq!(
+        claim_refund(&mut protocol, &project, alice, alice_ofund),
+        Err(program_error(OtonomError::AlreadyRefunded))
+    );
+
+    // Bob's deposit is still in escrow
+    assert_eq!(
+        close_project(&mut protocol, &project),
+        Err(program_error(OtonomError::VaultNotEmpty))
+    );
+
+    claim_refund(&mut protocol, &project, bob, bob_ofund).unwrap();
+    assert_eq!(protocol.env.token_balance(&bob_ofund), grant);
+    let state: Project = protocol.env.state(&project.project);
+    assert_eq!(state.open_contributions, 0);
+    assert_eq!(state.total_raised, 0);
+    assert_eq!(protocol.env.token_balance(&project.vault), 0);
+
+    let vault_rent = protocol.env.lamports(&project.vault);
+    let authority_lamports = protocol.env.lamports(&owner);
+    close_project(&mut protocol, &project).unwrap();
+    assert!(!protocol.env.exists(&project.project));
+    assert!(!protocol.env.exists(&project.vault));
+    assert!(protocol.env.lamports(&owner) > authority_lamports + vault_rent);
+}
@@ -0,0 +1,139 @@
+mod common;
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use common::{constraint_error, pda, program_error, Env, Protocol};
+use ofund_token::{
+    accounts, instruction, OtonomError, ProgramConfig, Project, TierBenefit, TierConfig, TierLevel,
+};
+
+fn refresh_tier(
+    protocol: &mut Protocol,
+    user: &Pubkey,
+    tier_config: Pubkey,
+) -> std::result::Result<(), ProgramError> {
+    let accounts = accounts::RefreshTier {
+        user_profile: protocol.user_profile(user),
+        program_config: protocol.program_config(),
+        tier_config,
+    };
+    protocol.env.process(accounts, instruction::RefreshTier {})
+}
+
+#[test]
+fn refresh_tier_only_reads_the_ofund_tier_table() {
+    let mut protocol = Protocol::new();
+    let alice = protocol.env.wallet();
+    protocol.register(alice);
+
+    // A tier table for another mint, placing everyone in its top level
+    let other_mint = Pubkey::new_unique();
+    let (fake_tier_config, bump) =
+        Pubkey::find_program_address(&[b"tier-config", other_mint.as_ref()], &ofund_token::ID);
+    let level = |threshold: u64| TierLevel {
+        threshold,
+        name: "Whale".to_string(),
+        lottery_tickets: 1,
+    };
+    let fake = TierConfig {
+        bump,
+        mint: other_mint,
+        tiers: vec![level(0), level(1), level(2)],
+    };
+    protocol.env.set_state(fake_tier_config, &fake, 1_000);
+
+    assert_eq!(
+        refresh_tier(&mut protocol, &alice, fake_tier_config),
+        Err(constraint_error(ErrorCode::ConstraintSeeds))
+    );
+
+    let tier_config = protocol.tier_config();
+    refresh_tier(&mut protocol, &alice, tier_config).unwrap();
+    let profile: ofund_token::UserProfile = protocol.env.state(&protocol.user_profile(&alice));
+    assert_eq!(profile.tier, 0);
+}
+
+#[test]
+fn initialize_program_config_requires_the_upgrade_authority() {
+    let mut env = Env::new();
+    let upgrade_authority = env.wallet();
+    let intruder = env.wallet();
+    env.set_upgrade_authority(Some(upgrade_authority));
+
+    let mint = Pubkey::new_unique();
+    let (mint_authority, bump) =
+        Pubkey::find_program_address(&[b"authority", mint.as_ref()], &ofund_token::ID);
+    let state = ofund_token::MintAuthority {
+        bump,
+        mint,
+        admin: intruder,
+        token_name: String::new(),
+        token_symbol: String::new(),
+        token_uri: String::new(),
+        is_initialized: true,
+    };
+    env.set_state(mint_authority, &state, 300);
+
+    let (program_data, program_config) = (env.program_data(), pda(&[b"program-config"]));
+    let accounts = |authority| accounts::InitializeProgramConfig {
+        authority,
+        program: ofund_token::ID,
+        program_data,
+        mint_authority,
+        program_config,
+        system_program: system_program::ID,
+    };
+
+    // The mint's own admin is not enough
+    assert_eq!(
+        env.process(accounts(intruder), instruction::InitializeProgramConfig {}),
+        Err(program_error(OtonomError::Unauthorized))
+    );
+
+    env.process(accounts(upgrade_authority), instruction::InitializeProgramConfig {})
+        .unwrap();
+    let config: ProgramConfig = env.state(&program_config);
+    assert_eq!(config.ofund_mint, mint);
+
+    // The canonical mint is set once
+    assert!(env
+        .process(accounts(upgrade_authority), instruction::InitializeProgramConfig {})
+        .is_err());
+}
+
+#[test]
+fn every_tier_level_can_have_its_own_benefit() {
+    let mut protocol = Protocol::new();
+    let owner = protocol.env.wallet();
+    let ofund = protocol.mint;
+    let project = protocol.create_project(owner, ofund);
+    let configure = |protocol: &mut Protocol, count: u16| {
+        let benefits = (0..count)
+            .map(|i| TierBenefit {
+                weight_bps: 10_000 + i * 1_000,
+                access_delay: 0,
+            })
+            .collect();
+        protocol.env.process(
+            accounts::ConfigureTierBenefits {
+                authority: owner,
+                project: project.project,
+            },
+            instruction::ConfigureTierBenefits {
+                benefits,
+                max_allocation: 0,
+            },
+        )
+    };
+
+    // One more than the tier table can hold
+    assert_eq!(
+        configure(&mut protocol, 17),
+        Err(program_error(OtonomError::InvalidTierBenefits))
+    );
+    configure(&mut protocol, 16).unwrap();
+    let state: Project = protocol.env.state(&project.project);
+    assert_eq!(state.tier_benefits.len(), 16);
+    assert_eq!(state.tier_benefits[15].weight_bps, 25_000);
+}
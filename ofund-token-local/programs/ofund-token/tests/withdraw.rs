@@ -0,0 +1,123 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token;
+use common::{program_error, ProjectKeys, Protocol};
+use ofund_token::{
+    accounts, instruction, Contribution, GlobalStats, OtonomError, Project, UserProfile,
+};
+
+const OFUND: u64 = 1_000_000_000;
+const GRANT: u64 = 100_000 * OFUND;
+
+fn configure_exit_penalty(
+    protocol: &mut Protocol,
+    project: &ProjectKeys,
+    penalty_bps: u16,
+    treasury: Pubkey,
+) -> std::result::Result<(), ProgramError> {
+    let accounts = accounts::ConfigureExitPenalty {
+        fee_manager: protocol.admin,
+        project: project.project,
+        mint_authority: protocol.mint_authority,
+        roles: None,
+    };
+    protocol.env.process(
+        accounts,
+        instruction::ConfigureExitPenalty {
+            penalty_bps,
+            treasury,
+        },
+    )
+}
+
+fn withdraw(
+    protocol: &mut Protocol,
+    project: &ProjectKeys,
+    investor: Pubkey,
+    investor_token_account: Pubkey,
+    treasury_token_account: Option<Pubkey>,
+    amount: u64,
+) -> std::result::Result<(), ProgramError> {
+    let accounts = accounts::WithdrawInvestment {
+        investor,
+        project: project.project,
+        contribution: project.contribution(&investor),
+        user_profile: protocol.user_profile(&investor),
+        project_vault: project.vault,
+        investor_token_account,
+        treasury_token_account,
+        payment_mint: project.payment_mint,
+        lottery_ticket: None,
+        global_stats: project.global_stats,
+        program_config: protocol.program_config(),
+        tier_config: protocol.tier_config(),
+        token_program: spl_token::ID,
+    };
+    protocol
+        .env
+        .process(accounts, instruction::WithdrawInvestment { amount })
+}
+
+#[test]
+fn withdrawals_pay_the_exit_penalty_and_unwind_the_investment() {
+    let mut protocol = Protocol::new();
+    let owner = protocol.env.wallet();
+    let alice = protocol.env.wallet();
+    let alice_ofund = protocol.register(alice);
+    let (admin, mint) = (protocol.admin, protocol.mint);
+    let treasury = protocol.env.create_token_account(&mint, &admin, 0);
+
+    let project = protocol.open_project(owner, 1_000);
+    configure_exit_penalty(&mut protocol, &project, 500, treasury).unwrap();
+    protocol.invest(alice, alice_ofund, &project, 1_000 * OFUND).unwrap();
+
+    // Investors keep the penalty they joined under
+    assert_eq!(
+        configure_exit_penalty(&mut protocol, &project, 0, treasury),
+        Err(program_error(OtonomError::SaleAlreadyStarted))
+    );
+    assert_eq!(
+        withdraw(&mut protocol, &project, alice, alice_ofund, None, 200 * OFUND),
+        Err(program_error(OtonomError::TreasuryRequired))
+    );
+
+    withdraw(&mut protocol, &project, alice, alice_ofund, Some(treasury), 200 * OFUND).unwrap();
+    assert_eq!(protocol.env.token_balance(&alice_ofund), GRANT - 810 * OFUND);
+    assert_eq!(protocol.env.token_balance(&treasury), 10 * OFUND);
+    let contribution: Contribution = protocol.env.state(&project.contribution(&alice));
+    assert_eq!(contribution.amount, 800 * OFUND);
+    let profile: UserProfile = protocol.env.state(&protocol.user_profile(&alice));
+    assert_eq!(profile.total_invested, 800 * OFUND);
+    let stats: GlobalStats = protocol.env.state(&project.global_stats);
+    assert_eq!(stats.total_ofund_raised, 800 * OFUND);
+    assert_eq!(stats.total_ofund_penalties, 10 * OFUND);
+
+    assert_eq!(
+        withdraw(&mut protocol, &project, alice, alice_ofund, Some(treasury), 801 * OFUND),
+        Err(program_error(OtonomError::InsufficientContribution))
+    );
+    withdraw(&mut protocol, &project, alice, alice_ofund, Some(treasury), 800 * OFUND).unwrap();
+    let state: Project = protocol.env.state(&project.project);
+    assert_eq!(state.total_raised, 0);
+    assert_eq!(state.open_contributions, 0);
+    assert_eq!(protocol.env.token_balance(&project.vault), 0);
+}
+
+#[test]
+fn withdrawals_close_with_the_sale() {
+    let mut protocol = Protocol::new();
+    let owner = protocol.env.wallet();
+    let alice = protocol.env.wallet();
+    let alice_ofund = protocol.register(alice);
+
+    let project = protocol.open_project(owner, 1_000);
+    protocol.invest(alice, alice_ofund, &project, 100 * OFUND).unwrap();
+    protocol.env.set_time(protocol.env.now() + 1_000);
+
+    assert_eq!(
+        protocol.withdraw(alice, alice_ofund, &project, 100 * OFUND),
+        Err(program_error(OtonomError::SaleEnded))
+    );
+    assert_eq!(protocol.env.token_balance(&project.vault), 100 * OFUND);
+}